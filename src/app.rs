@@ -1,5 +1,14 @@
-use crate::pages::{get_pages, Page, PageOutput};
+use crate::clock::PageClock;
+use crate::frame_timing::FrameTiming;
+use crate::pages::params::Params;
+use crate::pages::{get_pages, PageAction, PageEntry, PageOutput, RenderContext};
+use std::io::ErrorKind;
 use std::time::Instant;
+use winit::keyboard::{Key, ModifiersState};
+
+/// Saved settings: `page = <id>` for the page to open, then one `[page-id]`
+/// section of parameters per page
+const SETTINGS_PATH: &str = "settings.cfg";
 
 pub struct AppState {
     pub current_page: usize,
@@ -9,55 +18,141 @@ pub struct AppState {
     pub auto_cycle: bool,
     pub cycle_interval: f32,
     pub last_cycle_time: Instant,
-    pub start_time: Instant,
-    pages: Vec<Box<dyn Page>>,
+    pub clock: PageClock,
+    pub frame_timing: FrameTiming,
+    pages: Vec<PageEntry>,
+    window_size: (u32, u32),
 }
 
 impl AppState {
     pub fn new() -> Self {
-        let now = Instant::now();
-        Self {
+        let mut state = Self {
             current_page: 0,
             max_brightness_nits: 1000.0,
             paper_white_nits: 200.0,
             show_ui: false,
             auto_cycle: false,
             cycle_interval: 5.0,
-            last_cycle_time: now,
-            start_time: now,
+            last_cycle_time: Instant::now(),
+            clock: PageClock::new(),
+            frame_timing: FrameTiming::new(),
             pages: get_pages(),
-        }
+            window_size: (0, 0),
+        };
+        state.load_settings();
+        state
+    }
+
+    pub fn pages(&self) -> &[PageEntry] {
+        &self.pages
     }
 
+    /// Number of enabled pages
     pub fn page_count(&self) -> usize {
-        self.pages.len()
+        self.pages.iter().filter(|entry| entry.enabled).count()
+    }
+
+    /// Position of the current page among the enabled ones, from 1, or
+    /// `None` if the current page is disabled
+    pub fn page_number(&self) -> Option<usize> {
+        match self.pages[self.current_page].enabled {
+            true => Some(self.pages[..=self.current_page].iter().filter(|entry| entry.enabled).count()),
+            false => None,
+        }
     }
 
     pub fn next_page(&mut self) {
-        self.current_page = (self.current_page + 1) % self.pages.len();
-        self.last_cycle_time = Instant::now();
+        self.step_page(1);
     }
 
     pub fn prev_page(&mut self) {
-        if self.current_page == 0 {
-            self.current_page = self.pages.len() - 1;
-        } else {
-            self.current_page -= 1;
+        self.step_page(self.pages.len() - 1);
+    }
+
+    /// Move `offset` entries forward, wrapping and skipping disabled pages
+    fn step_page(&mut self, offset: usize) {
+        let mut index = self.current_page;
+        for _ in 0..self.pages.len() {
+            index = (index + offset) % self.pages.len();
+            if self.pages[index].enabled {
+                self.select_page(index);
+                return;
+            }
+        }
+    }
+
+    pub fn select_page(&mut self, index: usize) {
+        if index != self.current_page {
+            self.pages[self.current_page].page.on_hide();
         }
+        self.current_page = index;
         self.last_cycle_time = Instant::now();
     }
 
+    /// Select a page by registry ID, including disabled pages
+    pub fn select_page_id(&mut self, id: &str) -> bool {
+        match self.pages.iter().position(|entry| entry.id == id) {
+            Some(index) => {
+                self.select_page(index);
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn toggle_ui(&mut self) {
         self.show_ui = !self.show_ui;
     }
 
     pub fn current_page_name(&self) -> &'static str {
-        self.pages[self.current_page].name()
+        self.pages[self.current_page].page.name()
+    }
+
+    pub fn current_page_entry(&self) -> &PageEntry {
+        &self.pages[self.current_page]
+    }
+
+    pub fn current_page_ui(&mut self, ui: &mut egui::Ui) {
+        self.pages[self.current_page].page.ui(ui);
+    }
+
+    pub fn current_page_params(&mut self) -> Option<&mut Params> {
+        self.pages[self.current_page].page.params_mut()
     }
 
     pub fn render_current_page(&self, width: u32, height: u32) -> PageOutput {
-        let elapsed_time = self.start_time.elapsed().as_secs_f32();
-        self.pages[self.current_page].render(width, height, self.max_brightness_nits, elapsed_time)
+        self.pages[self.current_page].page.render(&RenderContext {
+            width,
+            height,
+            max_brightness_nits: self.max_brightness_nits,
+            paper_white_nits: self.paper_white_nits,
+            clock: &self.clock,
+            presents: self.frame_timing.presents(),
+        })
+    }
+
+    /// Tell every page the window size, if it changed since the last call
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if (width, height) == self.window_size {
+            return;
+        }
+        self.window_size = (width, height);
+        for entry in &mut self.pages {
+            entry.page.resize(width, height);
+        }
+    }
+
+    pub fn handle_page_key(&mut self, key: &Key, modifiers: ModifiersState) {
+        match self.pages[self.current_page].page.on_key(key, modifiers) {
+            Some(PageAction::SetMaxBrightness(nits)) => {
+                self.max_brightness_nits = nits.clamp(100.0, 10000.0);
+            }
+            None => {}
+        }
+    }
+
+    pub fn handle_page_pointer(&mut self, pos: [f32; 2], pressed: bool) {
+        self.pages[self.current_page].page.on_pointer(pos, pressed);
     }
 
     pub fn update(&mut self) {
@@ -67,5 +162,78 @@ impl AppState {
                 self.next_page();
             }
         }
+
+        self.clock.tick();
+        self.pages[self.current_page].page.update();
+    }
+
+    /// Restore the page and page parameters saved by a previous run
+    fn load_settings(&mut self) {
+        let text = match std::fs::read_to_string(SETTINGS_PATH) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return,
+            Err(e) => {
+                eprintln!("Failed to read {}: {}", SETTINGS_PATH, e);
+                return;
+            }
+        };
+
+        let mut section = None;
+        for line in text.lines().map(str::trim) {
+            if let Some(id) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = Some(id);
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let Some(page_id) = section else {
+                if key == "page" && !self.select_page_id(value) {
+                    eprintln!("Unknown page {} in {}", value, SETTINGS_PATH);
+                }
+                continue;
+            };
+            let Some(entry) = self.pages.iter_mut().find(|entry| entry.id == page_id) else {
+                continue;
+            };
+            let Some(params) = entry.page.params_mut() else {
+                continue;
+            };
+            if let Some(param) = params.iter_mut().find(|p| p.id == key)
+                && !param.set_text(value)
+            {
+                eprintln!("Ignoring saved {} = {} for {}", key, value, page_id);
+            }
+        }
+    }
+
+    /// Called once before the app exits, so the current page can finish any
+    /// work in progress as it would when another page replaced it
+    pub fn exit(&mut self) {
+        self.pages[self.current_page].page.on_hide();
+        self.save_settings();
+    }
+
+    fn save_settings(&mut self) {
+        let mut text = format!("page = {}\n\n", self.pages[self.current_page].id);
+        for entry in &mut self.pages {
+            let Some(params) = entry.page.params_mut() else {
+                continue;
+            };
+            text += &format!("[{}]\n", entry.id);
+            for param in params.iter() {
+                text += &format!("{} = {}\n", param.id, param.to_text());
+            }
+            text.push('\n');
+        }
+        if let Err(e) = std::fs::write(SETTINGS_PATH, text) {
+            eprintln!("Failed to write {}: {}", SETTINGS_PATH, e);
+        }
+    }
+
+    /// Called after the frame has been presented
+    pub fn end_frame(&mut self) {
+        self.frame_timing.record_present();
     }
 }
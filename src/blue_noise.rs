@@ -0,0 +1,151 @@
+//! Blue-noise threshold maps for dithering, made with Ulichney's
+//! void-and-cluster method.
+
+/// Width in pixels of the Gaussian that measures clusters and voids
+const SIGMA: f32 = 1.5;
+/// Pixels further away than this get a negligible share of the Gaussian
+const RADIUS: isize = 6;
+
+/// Energy of a binary pattern: each set pixel adds a Gaussian centered on
+/// it, wrapping around the edges so the tile repeats seamlessly
+struct Energy {
+    size: usize,
+    kernel: Vec<f32>,
+    values: Vec<f32>,
+}
+
+impl Energy {
+    fn new(size: usize) -> Self {
+        let side = 2 * RADIUS + 1;
+        let kernel = (0..side * side)
+            .map(|i| {
+                let (dx, dy) = ((i % side - RADIUS) as f32, (i / side - RADIUS) as f32);
+                (-(dx * dx + dy * dy) / (2.0 * SIGMA * SIGMA)).exp()
+            })
+            .collect();
+        Self { size, kernel, values: vec![0.0; size * size] }
+    }
+
+    fn set(&mut self, pattern: &mut [bool], index: usize, on: bool) {
+        pattern[index] = on;
+        let sign = if on { 1.0 } else { -1.0 };
+        let size = self.size as isize;
+        let (x0, y0) = (index as isize % size, index as isize / size);
+        let mut weights = self.kernel.iter();
+        for dy in -RADIUS..=RADIUS {
+            for dx in -RADIUS..=RADIUS {
+                let x = (x0 + dx).rem_euclid(size);
+                let y = (y0 + dy).rem_euclid(size);
+                self.values[(y * size + x) as usize] += sign * weights.next().unwrap();
+            }
+        }
+    }
+
+    /// Set pixel with the most set pixels around it
+    fn tightest_cluster(&self, pattern: &[bool]) -> usize {
+        let set = (0..pattern.len()).filter(|&i| pattern[i]);
+        set.max_by(|&a, &b| self.values[a].total_cmp(&self.values[b])).unwrap_or(0)
+    }
+
+    /// Clear pixel with the fewest set pixels around it
+    fn largest_void(&self, pattern: &[bool]) -> usize {
+        let clear = (0..pattern.len()).filter(|&i| !pattern[i]);
+        clear.min_by(|&a, &b| self.values[a].total_cmp(&self.values[b])).unwrap_or(0)
+    }
+}
+
+/// Square tile of `size` x `size` dither thresholds in row order. Every
+/// value 0..=255 appears equally often when `size` is a multiple of 16, and
+/// thresholding at any level gives evenly spread pixels without clumps.
+pub fn void_and_cluster(size: usize) -> Vec<u8> {
+    let count = size * size;
+    let mut energy = Energy::new(size);
+    let mut pattern = vec![false; count];
+
+    // Start from a tenth of the pixels at pseudo-random positions
+    let mut seed = 0x9e37_79b9u32;
+    let mut initial = 0;
+    while initial < count / 10 {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        let index = seed as usize % count;
+        if !pattern[index] {
+            energy.set(&mut pattern, index, true);
+            initial += 1;
+        }
+    }
+
+    // Move the tightest cluster into the largest void until that is a no-op
+    for _ in 0..count {
+        let cluster = energy.tightest_cluster(&pattern);
+        energy.set(&mut pattern, cluster, false);
+        let void = energy.largest_void(&pattern);
+        energy.set(&mut pattern, void, true);
+        if void == cluster {
+            break;
+        }
+    }
+
+    // Rank the starting pixels by removing clusters, then the rest by
+    // filling voids
+    let mut rank = vec![0; count];
+    let (start_pattern, start_energy) = (pattern.clone(), energy.values.clone());
+    for r in (0..initial).rev() {
+        let cluster = energy.tightest_cluster(&pattern);
+        energy.set(&mut pattern, cluster, false);
+        rank[cluster] = r;
+    }
+    pattern = start_pattern;
+    energy.values = start_energy;
+    for r in initial..count {
+        let void = energy.largest_void(&pattern);
+        energy.set(&mut pattern, void, true);
+        rank[void] = r;
+    }
+
+    rank.into_iter().map(|r| (r * 256 / count) as u8).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_threshold_used_equally() {
+        let tile = void_and_cluster(32);
+        let mut histogram = [0; 256];
+        for value in &tile {
+            histogram[*value as usize] += 1;
+        }
+        assert!(histogram.iter().all(|&n| n == 32 * 32 / 256));
+    }
+
+    #[test]
+    fn thresholded_pixels_are_evenly_spread() {
+        // Local averages of a white-noise pattern vary by sqrt(p(1-p)/n);
+        // blue noise stays well below that at every level
+        let size = 64;
+        let tile = void_and_cluster(size);
+        for level in [32u8, 128, 224] {
+            let p = level as f32 / 256.0;
+            let block = 8;
+            let mut variance = 0.0;
+            let blocks = (size / block) * (size / block);
+            for by in 0..size / block {
+                for bx in 0..size / block {
+                    let mut on = 0;
+                    for y in by * block..(by + 1) * block {
+                        for x in bx * block..(bx + 1) * block {
+                            on += (tile[y * size + x] < level) as u32;
+                        }
+                    }
+                    let mean = on as f32 / (block * block) as f32;
+                    variance += (mean - p) * (mean - p) / blocks as f32;
+                }
+            }
+            let white = p * (1.0 - p) / (block * block) as f32;
+            assert!(variance < white * 0.25, "level {level}: {variance} vs {white}");
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! The clock pages animate from. Unlike wall time it can be paused, stepped
+//! a frame at a time, run at a fixed timestep, or scrubbed to any time.
+
+use std::time::Instant;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockMode {
+    /// Advance by the wall time between frames
+    RealTime,
+    /// Advance exactly one step per frame, however long frames take
+    FixedStep,
+    /// Stand still until stepped or scrubbed
+    Paused,
+}
+
+impl ClockMode {
+    pub const ALL: [ClockMode; 3] = [ClockMode::RealTime, ClockMode::FixedStep, ClockMode::Paused];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ClockMode::RealTime => "Real time",
+            ClockMode::FixedStep => "Fixed step",
+            ClockMode::Paused => "Paused",
+        }
+    }
+}
+
+pub struct PageClock {
+    pub mode: ClockMode,
+    /// Frames per second of the fixed timestep, also used for single steps
+    pub step_rate_hz: f64,
+    /// Mode to go back to when unpausing
+    resume_mode: ClockMode,
+    /// Seconds, kept in f64 so soak tests don't lose precision
+    time: f64,
+    frame: u64,
+    last_tick: Option<Instant>,
+}
+
+impl PageClock {
+    pub fn new() -> Self {
+        Self {
+            mode: ClockMode::RealTime,
+            step_rate_hz: 60.0,
+            resume_mode: ClockMode::RealTime,
+            time: 0.0,
+            frame: 0,
+            last_tick: None,
+        }
+    }
+
+    /// Page time in seconds
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Number of frames the clock has advanced, including single steps
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    fn step_seconds(&self) -> f64 {
+        1.0 / self.step_rate_hz.max(1.0)
+    }
+
+    /// Called once per rendered frame
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        let wall = self.last_tick.map_or(0.0, |last| (now - last).as_secs_f64());
+        self.last_tick = Some(now);
+
+        match self.mode {
+            ClockMode::RealTime => {
+                self.time += wall;
+                self.frame += 1;
+            }
+            ClockMode::FixedStep => {
+                // Accumulated rather than derived from `frame`, so changing
+                // the rate mid-run doesn't rescale the time already elapsed
+                self.time += self.step_seconds();
+                self.frame += 1;
+            }
+            ClockMode::Paused => {}
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: ClockMode) {
+        if mode != ClockMode::Paused {
+            self.resume_mode = mode;
+        }
+        self.mode = mode;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        if self.mode == ClockMode::Paused {
+            self.set_mode(self.resume_mode);
+        } else {
+            self.set_mode(ClockMode::Paused);
+        }
+    }
+
+    /// Pause and move by whole steps, backwards for negative `frames`
+    pub fn step(&mut self, frames: i64) {
+        self.set_mode(ClockMode::Paused);
+        self.frame = self.frame.saturating_add_signed(frames);
+        self.time = (self.time + frames as f64 * self.step_seconds()).max(0.0);
+    }
+
+    /// Pause at the given time, snapping the frame counter to the step grid
+    pub fn scrub(&mut self, time: f64) {
+        self.set_mode(ClockMode::Paused);
+        self.time = time.max(0.0);
+        self.frame = (self.time / self.step_seconds()).round() as u64;
+    }
+
+    /// Back to time and frame zero, keeping the mode
+    pub fn reset(&mut self) {
+        self.time = 0.0;
+        self.frame = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_step_ignores_wall_time() {
+        let mut clock = PageClock::new();
+        clock.set_mode(ClockMode::FixedStep);
+        clock.step_rate_hz = 120.0;
+        for _ in 0..240 {
+            clock.tick();
+        }
+        assert_eq!(clock.frame(), 240);
+        assert!((clock.time() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fixed_step_rate_change_keeps_elapsed_time() {
+        let mut clock = PageClock::new();
+        clock.set_mode(ClockMode::FixedStep);
+        for _ in 0..600 {
+            clock.tick();
+        }
+        clock.step_rate_hz = 120.0;
+        assert!((clock.time() - 10.0).abs() < 1e-9);
+        for _ in 0..120 {
+            clock.tick();
+        }
+        assert!((clock.time() - 11.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn step_after_real_time_moves_one_step() {
+        let mut clock = PageClock::new();
+        // Real time counts display frames, which aren't on the step grid
+        for _ in 0..144 {
+            clock.tick();
+        }
+        let before = clock.time();
+        clock.step(1);
+        assert!((clock.time() - before - 1.0 / 60.0).abs() < 1e-9);
+        clock.step(-1);
+        assert!((clock.time() - before).abs() < 1e-9);
+    }
+
+    #[test]
+    fn paused_clock_only_moves_when_stepped() {
+        let mut clock = PageClock::new();
+        clock.scrub(1.0);
+        clock.tick();
+        assert_eq!((clock.frame(), clock.time()), (60, 1.0));
+        clock.step(1);
+        clock.step(1);
+        clock.step(-1);
+        assert_eq!(clock.frame(), 61);
+        assert_eq!(clock.mode, ClockMode::Paused);
+
+        clock.step(-100);
+        assert_eq!((clock.frame(), clock.time()), (0, 0.0));
+    }
+
+    #[test]
+    fn unpause_restores_previous_mode() {
+        let mut clock = PageClock::new();
+        clock.set_mode(ClockMode::FixedStep);
+        clock.toggle_pause();
+        assert_eq!(clock.mode, ClockMode::Paused);
+        clock.toggle_pause();
+        assert_eq!(clock.mode, ClockMode::FixedStep);
+    }
+}
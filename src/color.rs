@@ -0,0 +1,376 @@
+//! Color primaries and conversion of wide-gamut colors into scRGB.
+//!
+//! scRGB is linear light with BT.709 primaries and a D65 white, where 1.0 is
+//! 80 nits. Colors outside the BT.709 gamut come out with negative components,
+//! which the FP16 swapchain passes through to the display.
+
+use crate::transfer::pq;
+use glam::{Mat3, Vec3};
+
+/// A set of RGB primaries and a white point, as CIE 1931 xy chromaticities
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorSpace {
+    pub name: &'static str,
+    pub red: [f32; 2],
+    pub green: [f32; 2],
+    pub blue: [f32; 2],
+    pub white: [f32; 2],
+}
+
+pub const D65: [f32; 2] = [0.3127, 0.3290];
+pub const D50: [f32; 2] = [0.3457, 0.3585];
+pub const DCI_WHITE: [f32; 2] = [0.314, 0.351];
+pub const ACES_WHITE: [f32; 2] = [0.32168, 0.33767];
+
+pub const BT709: ColorSpace = ColorSpace {
+    name: "BT.709",
+    red: [0.640, 0.330],
+    green: [0.300, 0.600],
+    blue: [0.150, 0.060],
+    white: D65,
+};
+
+pub const DCI_P3: ColorSpace = ColorSpace {
+    name: "DCI-P3",
+    red: [0.680, 0.320],
+    green: [0.265, 0.690],
+    blue: [0.150, 0.060],
+    white: DCI_WHITE,
+};
+
+pub const DISPLAY_P3: ColorSpace = ColorSpace {
+    name: "Display P3",
+    white: D65,
+    ..DCI_P3
+};
+
+pub const BT2020: ColorSpace = ColorSpace {
+    name: "BT.2020",
+    red: [0.708, 0.292],
+    green: [0.170, 0.797],
+    blue: [0.131, 0.046],
+    white: D65,
+};
+
+pub const ACES_AP0: ColorSpace = ColorSpace {
+    name: "ACES AP0",
+    red: [0.7347, 0.2653],
+    green: [0.0, 1.0],
+    blue: [0.0001, -0.0770],
+    white: ACES_WHITE,
+};
+
+pub const ACES_AP1: ColorSpace = ColorSpace {
+    name: "ACES AP1",
+    red: [0.713, 0.293],
+    green: [0.165, 0.830],
+    blue: [0.128, 0.044],
+    white: ACES_WHITE,
+};
+
+/// CIE 1931 2° spectral locus, xy chromaticity from 380nm to 700nm in 5nm steps
+pub const SPECTRAL_LOCUS: [[f32; 2]; 65] = [
+    [0.1741, 0.0050], // 380nm
+    [0.1740, 0.0050], // 385nm
+    [0.1738, 0.0049], // 390nm
+    [0.1736, 0.0049], // 395nm
+    [0.1733, 0.0048], // 400nm
+    [0.1730, 0.0048], // 405nm
+    [0.1726, 0.0048], // 410nm
+    [0.1721, 0.0048], // 415nm
+    [0.1714, 0.0051], // 420nm
+    [0.1703, 0.0058], // 425nm
+    [0.1689, 0.0069], // 430nm
+    [0.1669, 0.0086], // 435nm
+    [0.1644, 0.0109], // 440nm
+    [0.1611, 0.0138], // 445nm
+    [0.1566, 0.0177], // 450nm
+    [0.1510, 0.0227], // 455nm
+    [0.1440, 0.0297], // 460nm
+    [0.1355, 0.0399], // 465nm
+    [0.1241, 0.0578], // 470nm
+    [0.1096, 0.0868], // 475nm
+    [0.0913, 0.1327], // 480nm
+    [0.0687, 0.2007], // 485nm
+    [0.0454, 0.2950], // 490nm
+    [0.0235, 0.4127], // 495nm
+    [0.0082, 0.5384], // 500nm
+    [0.0039, 0.6548], // 505nm
+    [0.0139, 0.7502], // 510nm
+    [0.0389, 0.8120], // 515nm
+    [0.0743, 0.8338], // 520nm
+    [0.1142, 0.8262], // 525nm
+    [0.1547, 0.8059], // 530nm
+    [0.1929, 0.7816], // 535nm
+    [0.2296, 0.7543], // 540nm
+    [0.2658, 0.7243], // 545nm
+    [0.3016, 0.6923], // 550nm
+    [0.3373, 0.6589], // 555nm
+    [0.3731, 0.6245], // 560nm
+    [0.4087, 0.5896], // 565nm
+    [0.4441, 0.5547], // 570nm
+    [0.4788, 0.5202], // 575nm
+    [0.5125, 0.4866], // 580nm
+    [0.5448, 0.4544], // 585nm
+    [0.5752, 0.4242], // 590nm
+    [0.6029, 0.3965], // 595nm
+    [0.6270, 0.3725], // 600nm
+    [0.6482, 0.3514], // 605nm
+    [0.6658, 0.3340], // 610nm
+    [0.6801, 0.3197], // 615nm
+    [0.6915, 0.3083], // 620nm
+    [0.7006, 0.2993], // 625nm
+    [0.7079, 0.2920], // 630nm
+    [0.7140, 0.2859], // 635nm
+    [0.7190, 0.2809], // 640nm
+    [0.7230, 0.2770], // 645nm
+    [0.7260, 0.2740], // 650nm
+    [0.7283, 0.2717], // 655nm
+    [0.7300, 0.2700], // 660nm
+    [0.7311, 0.2689], // 665nm
+    [0.7320, 0.2680], // 670nm
+    [0.7327, 0.2673], // 675nm
+    [0.7334, 0.2666], // 680nm
+    [0.7340, 0.2660], // 685nm
+    [0.7344, 0.2656], // 690nm
+    [0.7346, 0.2654], // 695nm
+    [0.7347, 0.2653], // 700nm
+];
+
+/// Bradford cone response matrix, used for chromatic adaptation
+const BRADFORD: Mat3 = Mat3::from_cols_array(&[
+    0.8951, -0.7502, 0.0389,
+    0.2664, 1.7135, -0.0685,
+    -0.1614, 0.0367, 1.0296,
+]);
+
+/// BT.2100 linear BT.2020 RGB to LMS, for ICtCp
+const ICTCP_LMS: Mat3 = Mat3::from_cols_array(&[
+    1688.0 / 4096.0, 683.0 / 4096.0, 99.0 / 4096.0,
+    2146.0 / 4096.0, 2951.0 / 4096.0, 309.0 / 4096.0,
+    262.0 / 4096.0, 462.0 / 4096.0, 3688.0 / 4096.0,
+]);
+
+/// BT.2100 PQ-encoded L'M'S' to ICtCp
+const ICTCP_FROM_LMS: Mat3 = Mat3::from_cols_array(&[
+    2048.0 / 4096.0, 6610.0 / 4096.0, 17933.0 / 4096.0,
+    2048.0 / 4096.0, -13613.0 / 4096.0, -17390.0 / 4096.0,
+    0.0, 7003.0 / 4096.0, -543.0 / 4096.0,
+]);
+
+/// CIE 1931 xy chromaticity to XYZ with Y = 1
+pub fn xy_to_xyz(xy: [f32; 2]) -> Vec3 {
+    Vec3::new(xy[0] / xy[1], 1.0, (1.0 - xy[0] - xy[1]) / xy[1])
+}
+
+/// XYZ to CIE 1931 xy chromaticity
+pub fn xyz_to_xy(xyz: Vec3) -> [f32; 2] {
+    let sum = xyz.x + xyz.y + xyz.z;
+    if sum == 0.0 {
+        return D65;
+    }
+    [xyz.x / sum, xyz.y / sum]
+}
+
+/// CIE 1931 xy chromaticity to CIE 1976 u'v'
+pub fn xy_to_uv_prime(xy: [f32; 2]) -> [f32; 2] {
+    let d = -2.0 * xy[0] + 12.0 * xy[1] + 3.0;
+    [4.0 * xy[0] / d, 9.0 * xy[1] / d]
+}
+
+/// CIELAB to XYZ relative to `white`, with the white at Y = 1
+pub fn lab_to_xyz(lab: [f32; 3], white: [f32; 2]) -> Vec3 {
+    let fy = (lab[0] + 16.0) / 116.0;
+    let fx = fy + lab[1] / 500.0;
+    let fz = fy - lab[2] / 200.0;
+    let finv = |t: f32| {
+        if t > 6.0 / 29.0 {
+            t * t * t
+        } else {
+            3.0 * (6.0f32 / 29.0).powi(2) * (t - 4.0 / 29.0)
+        }
+    };
+    Vec3::new(finv(fx), finv(fy), finv(fz)) * xy_to_xyz(white)
+}
+
+/// Bradford transform taking XYZ under `from` white to XYZ under `to` white
+pub fn chromatic_adaptation(from: [f32; 2], to: [f32; 2]) -> Mat3 {
+    if from == to {
+        return Mat3::IDENTITY;
+    }
+    let src = BRADFORD * xy_to_xyz(from);
+    let dst = BRADFORD * xy_to_xyz(to);
+    BRADFORD.inverse() * Mat3::from_diagonal(dst / src) * BRADFORD
+}
+
+impl ColorSpace {
+    /// Linear RGB to XYZ, normalized so RGB white has Y = 1
+    pub fn rgb_to_xyz(&self) -> Mat3 {
+        let primaries = Mat3::from_cols(
+            xy_to_xyz(self.red),
+            xy_to_xyz(self.green),
+            xy_to_xyz(self.blue),
+        );
+        let scale = primaries.inverse() * xy_to_xyz(self.white);
+        primaries * Mat3::from_diagonal(scale)
+    }
+
+    pub fn xyz_to_rgb(&self) -> Mat3 {
+        self.rgb_to_xyz().inverse()
+    }
+
+    /// Linear RGB in this space to linear RGB in `dst`, adapting white points
+    pub fn conversion_to(&self, dst: &ColorSpace) -> Mat3 {
+        dst.xyz_to_rgb() * chromatic_adaptation(self.white, dst.white) * self.rgb_to_xyz()
+    }
+
+    /// Relative luminance (Y) of linear RGB in this space
+    pub fn luminance(&self, rgb: [f32; 3]) -> f32 {
+        self.rgb_to_xyz().row(1).dot(Vec3::from(rgb))
+    }
+}
+
+/// Linear RGB color tagged with its color space, with components in nits
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Color {
+    pub space: &'static ColorSpace,
+    pub rgb: [f32; 3],
+}
+
+impl Color {
+    pub fn new(space: &'static ColorSpace, rgb_nits: [f32; 3]) -> Self {
+        Self { space, rgb: rgb_nits }
+    }
+
+    /// Color with the chromaticity of `rgb` scaled to a luminance of `nits`,
+    /// e.g. `Color::with_luminance(&BT2020, [1.0, 0.0, 0.0], 400.0)`
+    pub fn with_luminance(space: &'static ColorSpace, rgb: [f32; 3], nits: f32) -> Self {
+        let y = space.luminance(rgb);
+        let scale = if y > 0.0 { nits / y } else { 0.0 };
+        Self::new(space, rgb.map(|c| c * scale))
+    }
+
+    pub fn gray(nits: f32) -> Self {
+        Self::new(&BT709, [nits; 3])
+    }
+
+    /// Luminance in nits
+    pub fn luminance(&self) -> f32 {
+        self.space.luminance(self.rgb)
+    }
+
+    pub fn convert(&self, dst: &'static ColorSpace) -> Color {
+        if std::ptr::eq(self.space, dst) {
+            return *self;
+        }
+        let rgb = self.space.conversion_to(dst) * Vec3::from(self.rgb);
+        Color::new(dst, rgb.to_array())
+    }
+
+    /// PQ-based ICtCp color of absolute luminance, per BT.2100
+    pub fn from_ictcp(ictcp: [f32; 3]) -> Self {
+        let lms = ICTCP_FROM_LMS.inverse() * Vec3::from(ictcp);
+        let rgb = ICTCP_LMS.inverse() * Vec3::from(lms.to_array().map(pq::eotf));
+        Self::new(&BT2020, rgb.to_array())
+    }
+
+    pub fn ictcp(&self) -> [f32; 3] {
+        let rgb = self.convert(&BT2020).rgb;
+        let lms = ICTCP_LMS * Vec3::from(rgb);
+        (ICTCP_FROM_LMS * Vec3::from(lms.to_array().map(pq::inverse_eotf))).to_array()
+    }
+
+    /// Whether the color is reproducible with the primaries of `space`
+    pub fn is_inside(&self, space: &'static ColorSpace) -> bool {
+        let rgb = self.convert(space).rgb;
+        let tolerance = 1e-4 * rgb.iter().fold(1.0f32, |max, c| max.max(c.abs()));
+        rgb.iter().all(|&c| c >= -tolerance)
+    }
+
+    /// scRGB vertex color, with negative components where out of BT.709 gamut
+    pub fn to_scrgb(self) -> [f32; 4] {
+        let rgb = self.convert(&BT709).rgb;
+        [rgb[0] / 80.0, rgb[1] / 80.0, rgb[2] / 80.0, 1.0]
+    }
+}
+
+impl From<Color> for [f32; 4] {
+    fn from(color: Color) -> Self {
+        color.to_scrgb()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_mat_eq(m: Mat3, expected: [[f32; 3]; 3]) {
+        for (r, row) in expected.iter().enumerate() {
+            for (c, &value) in row.iter().enumerate() {
+                let actual = m.row(r)[c];
+                assert!((actual - value).abs() < 1e-3, "[{r}][{c}] {actual} != {value}");
+            }
+        }
+    }
+
+    #[test]
+    fn bt709_to_xyz() {
+        assert_mat_eq(BT709.rgb_to_xyz(), [
+            [0.4124, 0.3576, 0.1805],
+            [0.2126, 0.7152, 0.0722],
+            [0.0193, 0.1192, 0.9505],
+        ]);
+    }
+
+    #[test]
+    fn bt2020_to_bt709() {
+        // ITU-R BT.2087
+        assert_mat_eq(BT2020.conversion_to(&BT709), [
+            [1.6605, -0.5876, -0.0728],
+            [-0.1246, 1.1329, -0.0083],
+            [-0.0182, -0.1006, 1.1187],
+        ]);
+    }
+
+    #[test]
+    fn luminance_is_preserved() {
+        let red = Color::with_luminance(&BT2020, [1.0, 0.0, 0.0], 400.0);
+        assert!((red.luminance() - 400.0).abs() < 0.01);
+        let scrgb = red.to_scrgb();
+        assert!(scrgb[1] < 0.0 && scrgb[2] < 0.0);
+        assert!((BT709.luminance([scrgb[0], scrgb[1], scrgb[2]]) * 80.0 - 400.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn white_stays_white_across_white_points() {
+        let white = Color::new(&ACES_AP0, [100.0; 3]).to_scrgb();
+        for c in &white[..3] {
+            assert!((c - 1.25).abs() < 1e-3, "{white:?}");
+        }
+    }
+
+    #[test]
+    fn lab_white_and_gray() {
+        let white = lab_to_xyz([100.0, 0.0, 0.0], D50);
+        let expected = xy_to_xyz(D50);
+        assert!((white - expected).abs().max_element() < 1e-5, "{white}");
+        // L* 50 is 18.4% reflectance
+        assert!((lab_to_xyz([50.0, 0.0, 0.0], D50).y - 0.1842).abs() < 1e-3);
+        // Dark values use the linear segment
+        assert!((lab_to_xyz([5.0, 0.0, 0.0], D65).y - 0.005535).abs() < 1e-5);
+    }
+
+    #[test]
+    fn ictcp_neutral_and_round_trip() {
+        let [i, ct, cp] = Color::gray(100.0).ictcp();
+        assert!((i - 0.5081).abs() < 1e-3, "{i}");
+        assert!(ct.abs() < 1e-3 && cp.abs() < 1e-3, "{ct} {cp}");
+
+        let color = Color::new(&BT2020, [300.0, 40.0, 5.0]);
+        let back = Color::from_ictcp(color.ictcp());
+        for (a, b) in color.rgb.iter().zip(back.rgb) {
+            assert!((a - b).abs() < 0.1, "{:?}", back.rgb);
+        }
+        assert!(color.is_inside(&BT2020) && !color.is_inside(&BT709));
+    }
+}
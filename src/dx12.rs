@@ -12,6 +12,11 @@ use windows::Win32::Graphics::Dxgi::*;
 use windows::Win32::System::Threading::*;
 
 const FRAME_COUNT: u32 = 2;
+/// Side of the tiled blue-noise dither texture
+const BLUE_NOISE_SIZE: u32 = 64;
+
+/// Aspect ratio of the letterboxed viewport HDR pages are drawn into
+pub const VIEWPORT_ASPECT: f32 = 16.0 / 9.0;
 
 pub struct Dx12State {
     pub device: ID3D12Device,
@@ -48,6 +53,12 @@ pub struct Dx12State {
     pub font_srv_heap: Option<ID3D12DescriptorHeap>,
     // Keep upload buffer alive until GPU finishes copy
     font_upload_buffer: Option<ID3D12Resource>,
+    // Blue-noise dither thresholds for HDR pages, copied from the upload
+    // buffer in the first frame
+    blue_noise_texture: ID3D12Resource,
+    blue_noise_upload_buffer: ID3D12Resource,
+    blue_noise_srv_heap: ID3D12DescriptorHeap,
+    blue_noise_copied: bool,
 }
 
 #[repr(C)]
@@ -174,6 +185,8 @@ impl Dx12State {
             // Create SDR render target for egui
             let (sdr_texture, sdr_rtv_heap, sdr_srv_heap) = create_sdr_render_target(&device, width, height)?;
 
+            let (blue_noise_texture, blue_noise_upload_buffer, blue_noise_srv_heap) = create_blue_noise_texture(&device)?;
+
             // Create upload buffer for vertex data (1MB should be enough)
             let upload_buffer_size = 1024 * 1024;
             let upload_buffer: ID3D12Resource = {
@@ -235,6 +248,10 @@ impl Dx12State {
                 font_texture: None,
                 font_srv_heap: None,
                 font_upload_buffer: None,
+                blue_noise_texture,
+                blue_noise_upload_buffer,
+                blue_noise_srv_heap,
+                blue_noise_copied: false,
             })
         }
     }
@@ -497,22 +514,7 @@ impl Dx12State {
 
     /// Calculate viewport for 16:9 aspect ratio with letterboxing/pillarboxing
     pub fn get_16_9_viewport(&self) -> (D3D12_VIEWPORT, RECT) {
-        const TARGET_ASPECT: f32 = 16.0 / 9.0;
-        let window_aspect = self.width as f32 / self.height as f32;
-
-        let (vp_width, vp_height, vp_x, vp_y) = if window_aspect > TARGET_ASPECT {
-            // Window is wider than 16:9 - pillarbox (black bars on sides)
-            let vp_height = self.height as f32;
-            let vp_width = vp_height * TARGET_ASPECT;
-            let vp_x = (self.width as f32 - vp_width) / 2.0;
-            (vp_width, vp_height, vp_x, 0.0)
-        } else {
-            // Window is taller than 16:9 - letterbox (black bars on top/bottom)
-            let vp_width = self.width as f32;
-            let vp_height = vp_width / TARGET_ASPECT;
-            let vp_y = (self.height as f32 - vp_height) / 2.0;
-            (vp_width, vp_height, 0.0, vp_y)
-        };
+        let (vp_width, vp_height, vp_x, vp_y) = letterbox_viewport(self.width, self.height);
 
         let viewport = D3D12_VIEWPORT {
             TopLeftX: vp_x,
@@ -551,10 +553,51 @@ impl Dx12State {
 
             allocator.Reset()?;
             self.command_list.Reset(allocator, None)?;
+
+            if !self.blue_noise_copied {
+                self.copy_blue_noise();
+                self.blue_noise_copied = true;
+            }
         }
         Ok(())
     }
 
+    /// Record the copy of the blue-noise thresholds into their texture
+    unsafe fn copy_blue_noise(&self) {
+        unsafe {
+            let dst = D3D12_TEXTURE_COPY_LOCATION {
+                pResource: ManuallyDrop::new(Some(self.blue_noise_texture.clone())),
+                Type: D3D12_TEXTURE_COPY_TYPE_SUBRESOURCE_INDEX,
+                Anonymous: D3D12_TEXTURE_COPY_LOCATION_0 {
+                    SubresourceIndex: 0,
+                },
+            };
+            let src = D3D12_TEXTURE_COPY_LOCATION {
+                pResource: ManuallyDrop::new(Some(self.blue_noise_upload_buffer.clone())),
+                Type: D3D12_TEXTURE_COPY_TYPE_PLACED_FOOTPRINT,
+                Anonymous: D3D12_TEXTURE_COPY_LOCATION_0 {
+                    PlacedFootprint: D3D12_PLACED_SUBRESOURCE_FOOTPRINT {
+                        Offset: 0,
+                        Footprint: D3D12_SUBRESOURCE_FOOTPRINT {
+                            Format: DXGI_FORMAT_R8_UNORM,
+                            Width: BLUE_NOISE_SIZE,
+                            Height: BLUE_NOISE_SIZE,
+                            Depth: 1,
+                            RowPitch: blue_noise_row_pitch(),
+                        },
+                    },
+                },
+            };
+            self.command_list.CopyTextureRegion(&dst, 0, 0, 0, &src, None);
+            resource_barrier(
+                &self.command_list,
+                &self.blue_noise_texture,
+                D3D12_RESOURCE_STATE_COPY_DEST,
+                D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+            );
+        }
+    }
+
     pub fn clear_render_target(&self, clear_color: [f32; 4]) {
         unsafe {
             let rtv_handle = D3D12_CPU_DESCRIPTOR_HANDLE {
@@ -602,6 +645,13 @@ impl Dx12State {
             self.command_list.SetPipelineState(&self.quad_pso);
             self.command_list.SetGraphicsRootSignature(&self.root_signature);
 
+            // Bind the blue-noise dither texture
+            self.command_list.SetDescriptorHeaps(&[Some(self.blue_noise_srv_heap.clone())]);
+            self.command_list.SetGraphicsRootDescriptorTable(
+                1,
+                self.blue_noise_srv_heap.GetGPUDescriptorHandleForHeapStart(),
+            );
+
             // Use 16:9 viewport with letterboxing/pillarboxing
             let (viewport, scissor) = self.get_16_9_viewport();
             self.command_list.RSSetViewports(&[viewport]);
@@ -872,6 +922,27 @@ impl Drop for Dx12State {
     }
 }
 
+/// Size and offset in pixels of the 16:9 viewport inside a window, as
+/// `(width, height, x, y)`. All four are whole pixels, so geometry snapped
+/// in viewport coordinates is also snapped on screen.
+pub fn letterbox_viewport(width: u32, height: u32) -> (f32, f32, f32, f32) {
+    let window_aspect = width as f32 / height as f32;
+
+    if window_aspect > VIEWPORT_ASPECT {
+        // Window is wider than 16:9 - pillarbox (black bars on sides)
+        let vp_height = height as f32;
+        let vp_width = (vp_height * VIEWPORT_ASPECT).round().min(width as f32);
+        let vp_x = ((width as f32 - vp_width) / 2.0).floor();
+        (vp_width, vp_height, vp_x, 0.0)
+    } else {
+        // Window is taller than 16:9 - letterbox (black bars on top/bottom)
+        let vp_width = width as f32;
+        let vp_height = (vp_width / VIEWPORT_ASPECT).round().min(height as f32);
+        let vp_y = ((height as f32 - vp_height) / 2.0).floor();
+        (vp_width, vp_height, 0.0, vp_y)
+    }
+}
+
 unsafe fn get_hardware_adapter(factory: &IDXGIFactory4) -> Result<IDXGIAdapter1> {
     unsafe {
         for i in 0.. {
@@ -1020,14 +1091,82 @@ fn create_quad_pso(device: &ID3D12Device, root_signature: &ID3D12RootSignature,
     "#;
 
     // Non-textured shader (for HDR pages)
+    // Solid quads pass uv = (1, 1). A larger uv.x asks for the color to be
+    // quantized per pixel to simulate an integer pipeline, packed as
+    // bits | encoding << 4 | dither << 6, with uv.y the nits of signal 1.0
+    // for the linear and gamma encodings (see pages::Quantization)
     let ps_source_solid = r#"
         struct PSInput {
             float4 position : SV_Position;
             float2 uv : TEXCOORD;
             float4 color : COLOR;
         };
+
+        float3 pq_encode(float3 nits) {
+            float3 y = pow(saturate(nits / 10000.0), 0.1593017578125);
+            return pow((0.8359375 + 18.8515625 * y) / (1.0 + 18.6875 * y), 78.84375);
+        }
+
+        float3 pq_decode(float3 e) {
+            float3 p = pow(saturate(e), 1.0 / 78.84375);
+            return 10000.0 * pow(max(p - 0.8359375, 0.0) / (18.8515625 - 18.6875 * p), 1.0 / 0.1593017578125);
+        }
+
+        // 8x8 Bayer matrix threshold in 0..1
+        float bayer8(uint2 p) {
+            uint x = p.x & 7;
+            uint xy = (p.x ^ p.y) & 7;
+            uint v = ((xy & 1) << 5) | ((x & 1) << 4) | ((xy & 2) << 2) | ((x & 2) << 1) | ((xy & 4) >> 1) | ((x & 4) >> 2);
+            return (v + 0.5) / 64.0;
+        }
+
+        // Void-and-cluster blue noise, tiled; each texel is one of 256
+        // equally common thresholds
+        Texture2D<float> blueNoise : register(t0);
+
+        float blue_noise(uint2 p) {
+            return (blueNoise.Load(int3(p & 63, 0)) * 255.0 + 0.5) / 256.0;
+        }
+
         float4 main(PSInput input) : SV_Target {
-            return input.color;
+            if (input.uv.x <= 1.0) {
+                return input.color;
+            }
+
+            uint mode = (uint)(input.uv.x + 0.5);
+            uint bits = mode & 15;
+            uint encoding = (mode >> 4) & 3;
+            uint dither = (mode >> 6) & 3;
+            float peak = input.uv.y;
+            float levels = exp2((float)bits) - 1.0;
+
+            float threshold = 0.5;
+            if (dither == 1) {
+                threshold = bayer8((uint2)input.position.xy);
+            } else if (dither == 2) {
+                threshold = blue_noise((uint2)input.position.xy);
+            }
+
+            float3 nits = input.color.rgb * 80.0;
+            float3 signal;
+            if (encoding == 2) {
+                signal = pq_encode(nits);
+            } else if (encoding == 1) {
+                signal = pow(saturate(nits / peak), 1.0 / 2.2);
+            } else {
+                signal = saturate(nits / peak);
+            }
+
+            signal = saturate(floor(signal * levels + threshold) / levels);
+
+            if (encoding == 2) {
+                nits = pq_decode(signal);
+            } else if (encoding == 1) {
+                nits = pow(signal, 2.2) * peak;
+            } else {
+                nits = signal * peak;
+            }
+            return float4(nits / 80.0, input.color.a);
         }
     "#;
 
@@ -1285,6 +1424,102 @@ fn compile_shader(source: &str, entry_point: &str, target: &str) -> Result<ID3DB
     }
 }
 
+/// Upload rows are aligned to 256 bytes
+fn blue_noise_row_pitch() -> u32 {
+    (BLUE_NOISE_SIZE + 255) & !255
+}
+
+/// Blue-noise texture, waiting in COPY_DEST for the upload buffer holding
+/// its thresholds, and its SRV heap
+fn create_blue_noise_texture(
+    device: &ID3D12Device,
+) -> Result<(ID3D12Resource, ID3D12Resource, ID3D12DescriptorHeap)> {
+    let thresholds = crate::blue_noise::void_and_cluster(BLUE_NOISE_SIZE as usize);
+    let row_pitch = blue_noise_row_pitch();
+
+    unsafe {
+        let mut texture: Option<ID3D12Resource> = None;
+        device.CreateCommittedResource(
+            &D3D12_HEAP_PROPERTIES {
+                Type: D3D12_HEAP_TYPE_DEFAULT,
+                ..Default::default()
+            },
+            D3D12_HEAP_FLAG_NONE,
+            &D3D12_RESOURCE_DESC {
+                Dimension: D3D12_RESOURCE_DIMENSION_TEXTURE2D,
+                Width: BLUE_NOISE_SIZE as u64,
+                Height: BLUE_NOISE_SIZE,
+                DepthOrArraySize: 1,
+                MipLevels: 1,
+                Format: DXGI_FORMAT_R8_UNORM,
+                SampleDesc: DXGI_SAMPLE_DESC { Count: 1, Quality: 0 },
+                Layout: D3D12_TEXTURE_LAYOUT_UNKNOWN,
+                ..Default::default()
+            },
+            D3D12_RESOURCE_STATE_COPY_DEST,
+            None,
+            &mut texture,
+        )?;
+        let texture = texture.ok_or_else(|| anyhow!("Failed to create blue noise texture"))?;
+
+        let mut upload_buffer: Option<ID3D12Resource> = None;
+        device.CreateCommittedResource(
+            &D3D12_HEAP_PROPERTIES {
+                Type: D3D12_HEAP_TYPE_UPLOAD,
+                ..Default::default()
+            },
+            D3D12_HEAP_FLAG_NONE,
+            &D3D12_RESOURCE_DESC {
+                Dimension: D3D12_RESOURCE_DIMENSION_BUFFER,
+                Width: (row_pitch * BLUE_NOISE_SIZE) as u64,
+                Height: 1,
+                DepthOrArraySize: 1,
+                MipLevels: 1,
+                SampleDesc: DXGI_SAMPLE_DESC { Count: 1, Quality: 0 },
+                Layout: D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
+                ..Default::default()
+            },
+            D3D12_RESOURCE_STATE_GENERIC_READ,
+            None,
+            &mut upload_buffer,
+        )?;
+        let upload_buffer = upload_buffer.ok_or_else(|| anyhow!("Failed to create upload buffer"))?;
+
+        let mut mapped: *mut std::ffi::c_void = std::ptr::null_mut();
+        upload_buffer.Map(0, None, Some(&mut mapped))?;
+        let mapped = mapped as *mut u8;
+        for (y, row) in thresholds.chunks(BLUE_NOISE_SIZE as usize).enumerate() {
+            std::ptr::copy_nonoverlapping(row.as_ptr(), mapped.add(y * row_pitch as usize), row.len());
+        }
+        upload_buffer.Unmap(0, None);
+
+        let srv_heap: ID3D12DescriptorHeap = device.CreateDescriptorHeap(&D3D12_DESCRIPTOR_HEAP_DESC {
+            NumDescriptors: 1,
+            Type: D3D12_DESCRIPTOR_HEAP_TYPE_CBV_SRV_UAV,
+            Flags: D3D12_DESCRIPTOR_HEAP_FLAG_SHADER_VISIBLE,
+            ..Default::default()
+        })?;
+
+        device.CreateShaderResourceView(
+            &texture,
+            Some(&D3D12_SHADER_RESOURCE_VIEW_DESC {
+                Format: DXGI_FORMAT_R8_UNORM,
+                ViewDimension: D3D12_SRV_DIMENSION_TEXTURE2D,
+                Shader4ComponentMapping: D3D12_DEFAULT_SHADER_4_COMPONENT_MAPPING,
+                Anonymous: D3D12_SHADER_RESOURCE_VIEW_DESC_0 {
+                    Texture2D: D3D12_TEX2D_SRV {
+                        MipLevels: 1,
+                        ..Default::default()
+                    },
+                },
+            }),
+            srv_heap.GetCPUDescriptorHandleForHeapStart(),
+        );
+
+        Ok((texture, upload_buffer, srv_heap))
+    }
+}
+
 fn create_sdr_render_target(
     device: &ID3D12Device,
     width: u32,
@@ -0,0 +1,104 @@
+//! Present-interval tracking against the display's refresh rate.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Measures the time between presents and counts refreshes that were missed
+pub struct FrameTiming {
+    /// Refresh rate of the monitor showing the window, if the OS reports it
+    pub refresh_hz: Option<f32>,
+    pub dropped_frames: u64,
+    pub last_interval_ms: f32,
+    /// Signed difference between the last interval and the refresh period
+    pub last_deviation_ms: f32,
+    pub max_deviation_ms: f32,
+    pub log_path: PathBuf,
+    presents: u64,
+    last_present: Option<Instant>,
+    log: Option<BufWriter<File>>,
+}
+
+impl FrameTiming {
+    pub fn new() -> Self {
+        Self {
+            refresh_hz: None,
+            dropped_frames: 0,
+            last_interval_ms: 0.0,
+            last_deviation_ms: 0.0,
+            max_deviation_ms: 0.0,
+            log_path: PathBuf::from("frame_timing.csv"),
+            presents: 0,
+            last_present: None,
+            log: None,
+        }
+    }
+
+    /// Called right after each present
+    pub fn record_present(&mut self) {
+        let now = Instant::now();
+        let present = self.presents;
+        self.presents += 1;
+        let Some(last) = self.last_present.replace(now) else {
+            return;
+        };
+        let Some(refresh_hz) = self.refresh_hz else {
+            return;
+        };
+
+        let period_ms = 1000.0 / refresh_hz;
+        let interval_ms = (now - last).as_secs_f32() * 1000.0;
+        let deviation_ms = interval_ms - period_ms;
+        // Each whole extra refresh period is a frame the display repeated
+        let dropped = ((interval_ms / period_ms).round() as u64).saturating_sub(1);
+
+        self.last_interval_ms = interval_ms;
+        self.last_deviation_ms = deviation_ms;
+        self.max_deviation_ms = self.max_deviation_ms.max(deviation_ms.abs());
+        self.dropped_frames += dropped;
+
+        if let Some(log) = &mut self.log
+            && let Err(e) = writeln!(log, "{},{:.3},{:.3},{}", present, interval_ms, deviation_ms, dropped)
+        {
+            eprintln!("Failed to write {}: {}", self.log_path.display(), e);
+            self.log = None;
+        }
+    }
+
+    /// Number of presents since startup, unaffected by `reset`
+    pub fn presents(&self) -> u64 {
+        self.presents
+    }
+
+    /// Clear the counters, e.g. after the window was dragged or minimized
+    pub fn reset(&mut self) {
+        self.dropped_frames = 0;
+        self.max_deviation_ms = 0.0;
+        self.last_present = None;
+    }
+
+    pub fn is_logging(&self) -> bool {
+        self.log.is_some()
+    }
+
+    /// Start or stop writing one CSV row per frame to `log_path`
+    pub fn set_logging(&mut self, enabled: bool) {
+        if !enabled {
+            self.log = None;
+            return;
+        }
+        if self.log.is_some() {
+            return;
+        }
+        let result = File::create(&self.log_path).and_then(|file| {
+            let mut log = BufWriter::new(file);
+            writeln!(log, "present,interval_ms,deviation_ms,dropped")?;
+            Ok(log)
+        });
+        match result {
+            Ok(log) => self.log = Some(log),
+            Err(e) => eprintln!("Failed to create {}: {}", self.log_path.display(), e),
+        }
+    }
+}
@@ -1,10 +1,17 @@
 mod app;
+mod blue_noise;
+mod clock;
+mod color;
 mod dx12;
+mod frame_timing;
 mod pages;
+mod tonemap;
+mod transfer;
 mod ui;
 
 use anyhow::Result;
 use app::AppState;
+use clock::ClockMode;
 use dx12::Dx12State;
 use raw_window_handle::{HasWindowHandle, RawWindowHandle};
 use ui::UiState;
@@ -22,6 +29,7 @@ struct App {
     app_state: AppState,
     ui_state: UiState,
     modifiers: ModifiersState,
+    pointer_down: bool,
 }
 
 impl App {
@@ -32,15 +40,34 @@ impl App {
             app_state: AppState::new(),
             ui_state: UiState::new(),
             modifiers: ModifiersState::empty(),
+            pointer_down: false,
         }
     }
 
+    /// Forward the cursor to the current page in its NDC, unless egui has it
+    fn forward_pointer(&mut self) {
+        let (Some(dx12), Some(pos)) = (&self.dx12, self.ui_state.pointer_pos) else {
+            return;
+        };
+        if self.app_state.show_ui && self.ui_state.ctx.is_pointer_over_area() {
+            return;
+        }
+
+        let (viewport, _) = dx12.get_16_9_viewport();
+        let ndc = [
+            (pos.x - viewport.TopLeftX) / viewport.Width * 2.0 - 1.0,
+            1.0 - (pos.y - viewport.TopLeftY) / viewport.Height * 2.0,
+        ];
+        self.app_state.handle_page_pointer(ndc, self.pointer_down);
+    }
+
     fn render(&mut self) -> Result<()> {
         let dx12 = self.dx12.as_mut().unwrap();
         let width = dx12.width;
         let height = dx12.height;
 
         // Update app state (auto-cycle, etc.)
+        self.app_state.resize(width, height);
         self.app_state.update();
 
         // Begin frame
@@ -78,6 +105,7 @@ impl App {
 
         // End frame and present
         dx12.end_frame()?;
+        self.app_state.end_frame();
 
         Ok(())
     }
@@ -118,6 +146,7 @@ impl ApplicationHandler for App {
                 match Dx12State::new(hwnd, size.width, size.height) {
                     Ok(dx12) => {
                         self.dx12 = Some(dx12);
+                        self.app_state.frame_timing.refresh_hz = monitor_refresh_hz(&window);
                         self.window = Some(window);
                     }
                     Err(e) => {
@@ -145,13 +174,23 @@ impl ApplicationHandler for App {
                     }
                 }
             }
+            WindowEvent::Moved(_) => {
+                // The window may now be on a monitor with a different refresh rate
+                if let Some(window) = &self.window {
+                    self.app_state.frame_timing.refresh_hz = monitor_refresh_hz(window);
+                }
+            }
             WindowEvent::ModifiersChanged(mods) => {
                 self.modifiers = mods.state();
             }
             WindowEvent::CursorMoved { position, .. } => {
                 self.ui_state.on_mouse_move(position.x as f32, position.y as f32);
+                self.forward_pointer();
             }
             WindowEvent::MouseInput { state, button, .. } => {
+                if button == MouseButton::Left {
+                    self.pointer_down = state == ElementState::Pressed;
+                }
                 let egui_button = match button {
                     MouseButton::Left => egui::PointerButton::Primary,
                     MouseButton::Right => egui::PointerButton::Secondary,
@@ -159,6 +198,7 @@ impl ApplicationHandler for App {
                     _ => return,
                 };
                 self.ui_state.on_mouse_button(egui_button, state == ElementState::Pressed);
+                self.forward_pointer();
             }
             WindowEvent::MouseWheel { delta, .. } => {
                 let (dx, dy) = match delta {
@@ -185,10 +225,31 @@ impl ApplicationHandler for App {
                     Key::Character(c) if c.eq_ignore_ascii_case("u") && self.modifiers.control_key() => {
                         self.app_state.toggle_ui();
                     }
+                    Key::Character(c) if c.eq_ignore_ascii_case("p") && self.modifiers.control_key() => {
+                        self.app_state.clock.toggle_pause();
+                    }
+                    Key::Character(c) if c.eq_ignore_ascii_case("f") && self.modifiers.control_key() => {
+                        let mode = match self.app_state.clock.mode {
+                            ClockMode::FixedStep => ClockMode::RealTime,
+                            ClockMode::RealTime | ClockMode::Paused => ClockMode::FixedStep,
+                        };
+                        self.app_state.clock.set_mode(mode);
+                    }
+                    Key::Character(c) if c.as_str() == "0" && self.modifiers.control_key() => {
+                        self.app_state.clock.reset();
+                    }
+                    Key::Named(NamedKey::ArrowRight) if self.modifiers.control_key() => {
+                        self.app_state.clock.step(1);
+                    }
+                    Key::Named(NamedKey::ArrowLeft) if self.modifiers.control_key() => {
+                        self.app_state.clock.step(-1);
+                    }
                     Key::Named(NamedKey::Escape) => {
                         event_loop.exit();
                     }
-                    _ => {}
+                    _ => {
+                        self.app_state.handle_page_key(&logical_key, self.modifiers);
+                    }
                 }
             }
             WindowEvent::RedrawRequested => {
@@ -207,6 +268,10 @@ impl ApplicationHandler for App {
         }
     }
 
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        self.app_state.exit();
+    }
+
     fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
         if let Some(window) = &self.window {
             window.request_redraw();
@@ -214,11 +279,39 @@ impl ApplicationHandler for App {
     }
 }
 
+/// Refresh rate of the monitor the window is on
+fn monitor_refresh_hz(window: &Window) -> Option<f32> {
+    let millihertz = window.current_monitor()?.refresh_rate_millihertz()?;
+    Some(millihertz as f32 / 1000.0)
+}
+
 fn main() -> Result<()> {
+    let mut app = App::new();
+
+    // `--page <id>` opens a page, overriding the saved one; `--list-pages`
+    // prints the IDs and exits
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--page" => match args.next() {
+                Some(id) if app.app_state.select_page_id(&id) => {}
+                Some(id) => eprintln!("Unknown page: {}", id),
+                None => eprintln!("--page needs a page ID"),
+            },
+            "--list-pages" => {
+                for entry in app.app_state.pages() {
+                    let hidden = if entry.enabled { "" } else { " (hidden)" };
+                    println!("{:24} {:22} {}{}", entry.id, entry.category.name(), entry.description, hidden);
+                }
+                return Ok(());
+            }
+            _ => eprintln!("Ignoring unknown argument: {}", arg),
+        }
+    }
+
     let event_loop = EventLoop::new()?;
     event_loop.set_control_flow(ControlFlow::Poll);
 
-    let mut app = App::new();
     event_loop.run_app(&mut app)?;
 
     Ok(())
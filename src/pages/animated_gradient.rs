@@ -1,5 +1,6 @@
 use crate::ui::HdrTextLabel;
-use super::{Page, PageOutput, add_gradient_quad_h};
+use super::geometry::add_gradient_quad_h;
+use super::{Page, PageOutput, RenderContext};
 
 pub struct AnimatedGradient;
 
@@ -8,17 +9,18 @@ impl Page for AnimatedGradient {
         "Animated Color Gradient"
     }
 
-    fn render(&self, width: u32, height: u32, max_brightness_nits: f32, time: f32) -> PageOutput {
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
         let mut vertices = Vec::new();
 
-        let scale = height.min(width) as f32 / 1080.0;
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
         let font_size = (scale * 24.0).max(14.0);
+        let time = ctx.clock.time();
         let base = 0.25;
-        let r = base * (time * 2.0).sin() + base;
-        let g = base * (time * 1.0).sin() + base;
-        let b = base * (time * 0.5).sin() + base;
+        let r = base * (time * 2.0).sin() as f32 + base;
+        let g = base * (time * 1.0).sin() as f32 + base;
+        let b = base * (time * 0.5).sin() as f32 + base;
 
-        let max_scrgb = max_brightness_nits / 80.0;
+        let max_scrgb = ctx.max_brightness_nits / 80.0;
         let target_color = [r * max_scrgb, g * max_scrgb, b * max_scrgb, 1.0];
 
         let segments = 64;
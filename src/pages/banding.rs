@@ -0,0 +1,156 @@
+use crate::dx12::Vertex;
+use crate::transfer::{pq, BitDepth};
+use crate::ui::HdrTextLabel;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::geometry::add_gradient_quad_h;
+use super::layout::{GridLayout, Rect};
+use super::params::{Param, ParamId, Params};
+use super::{
+    Dither, Encoding, Page, PageAction, PageOutput, Quantization, RenderContext, format_nits,
+    nits_to_scrgb, quantize_vertices,
+};
+
+/// A slow PQ ramp repeated at several bit depths, with and without dither
+pub struct Banding {
+    params: Params,
+}
+
+const ENCODING: ParamId<usize> = ParamId::new("encoding");
+const START: ParamId<f32> = ParamId::new("start");
+const END: ParamId<f32> = ParamId::new("end");
+
+const BIT_DEPTHS: [BitDepth; 3] = [BitDepth::Eight, BitDepth::Ten, BitDepth::Twelve];
+/// Segments per ramp; each is linear in light, close enough to PQ over a short span
+const SEGMENTS: usize = 64;
+
+impl Default for Banding {
+    fn default() -> Self {
+        Self {
+            params: Params::new(vec![
+                Param::choice(ENCODING, "Encoding", Encoding::ALL.map(Encoding::name), 2),
+                Param::nits(START, "Start", 100.0, 0.01, 10000.0),
+                Param::nits(END, "End", 200.0, 0.01, 10000.0),
+            ]),
+        }
+    }
+}
+
+impl Banding {
+    fn encoding(&self) -> Encoding {
+        Encoding::ALL[self.params.choice(ENCODING)]
+    }
+
+    fn add_ramp(&self, vertices: &mut Vec<Vertex>, x0: f32, y0: f32, x1: f32, y1: f32) {
+        let start = pq::inverse_eotf(self.params.float(START));
+        let end = pq::inverse_eotf(self.params.float(END));
+        let scrgb_at = |t: f32| {
+            let value = nits_to_scrgb(pq::eotf(start + (end - start) * t));
+            [value, value, value, 1.0]
+        };
+        for segment in 0..SEGMENTS {
+            let t0 = segment as f32 / SEGMENTS as f32;
+            let t1 = (segment + 1) as f32 / SEGMENTS as f32;
+            let sx0 = x0 + (x1 - x0) * t0;
+            let sx1 = x0 + (x1 - x0) * t1;
+            add_gradient_quad_h(vertices, sx0, y0, sx1, y1, scrgb_at(t0), scrgb_at(t1));
+        }
+    }
+}
+
+impl Page for Banding {
+    fn name(&self) -> &'static str {
+        "Banding"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+
+        let encoding = self.encoding();
+        let rows = 1 + BIT_DEPTHS.len() * Dither::ALL.len();
+        let layout = GridLayout {
+            bounds: Rect { y1: -0.9, ..Rect::FULL },
+            cols: Some(1),
+            ..GridLayout::default()
+        };
+        let cells = layout.cells(rows, ctx.width, ctx.height);
+
+        let mut strip = |vertices: &mut Vec<Vertex>, row: usize, text: String| {
+            let cell = &cells[row];
+            labels.push(HdrTextLabel { text, x: cell.label.x0, y: cell.label.y0, nits: 40.0, size: font_size });
+            self.add_ramp(vertices, cell.rect.x0, cell.rect.y0, cell.rect.x1, cell.rect.y1);
+        };
+
+        strip(
+            &mut vertices,
+            0,
+            format!(
+                "Reference, unquantized: PQ ramp {} to {}nits",
+                format_nits(self.params.float(START)),
+                format_nits(self.params.float(END))
+            ),
+        );
+
+        let mut row = 1;
+        for bit_depth in BIT_DEPTHS {
+            for dither in Dither::ALL {
+                let first = vertices.len();
+                strip(
+                    &mut vertices,
+                    row,
+                    format!("{}-bit {}, {}", bit_depth.bits(), encoding.name(), dither.name()),
+                );
+                let quantization = Quantization { bit_depth, encoding, dither, peak_nits: ctx.max_brightness_nits };
+                quantize_vertices(&mut vertices[first..], quantization);
+                row += 1;
+            }
+        }
+
+        let signal_note = match encoding {
+            Encoding::Pq => String::new(),
+            Encoding::Linear | Encoding::Gamma22 => {
+                format!("  signal 1.0 = {}nits", format_nits(ctx.max_brightness_nits))
+            }
+        };
+        labels.push(HdrTextLabel {
+            text: format!("E: encoding  Up/Down: move ramp  Left/Right: ramp span{}", signal_note),
+            x: cells[0].rect.x0,
+            y: -0.94,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        let start = self.params.float(START);
+        let end = self.params.float(END);
+        match key {
+            Key::Character(c) if c.eq_ignore_ascii_case("e") => self.params.next_choice(ENCODING),
+            Key::Named(NamedKey::ArrowUp) if end * 2.0 <= 10000.0 => {
+                self.params.set(START, start * 2.0);
+                self.params.set(END, end * 2.0);
+            }
+            Key::Named(NamedKey::ArrowDown) if start / 2.0 >= 0.01 => {
+                self.params.set(START, start / 2.0);
+                self.params.set(END, end / 2.0);
+            }
+            Key::Named(NamedKey::ArrowRight) => {
+                self.params.set(END, (start + (end - start) * 2.0).min(10000.0));
+            }
+            Key::Named(NamedKey::ArrowLeft) => {
+                self.params.set(END, start + (end - start) / 2.0);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
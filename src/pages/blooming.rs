@@ -0,0 +1,183 @@
+use crate::ui::HdrTextLabel;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::geometry::{Canvas, add_circle};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageAction, PageOutput, RenderContext, VIEWPORT_ASPECT, add_quad, format_nits, nits_to_scrgb};
+
+/// Small bright objects on true black for judging local dimming halos.
+/// Sizes and speeds are in viewport heights, so the page looks the same at
+/// any window size.
+pub struct Blooming {
+    params: Params,
+    /// Position of the draggable dot in NDC
+    dot: [f32; 2],
+}
+
+const MODE: ParamId<usize> = ParamId::new("mode");
+const NITS: ParamId<f32> = ParamId::new("nits");
+/// Object edge length as a fraction of viewport height
+const SIZE: ParamId<f32> = ParamId::new("size");
+/// Viewport heights per second
+const SPEED: ParamId<f32> = ParamId::new("speed");
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BloomingMode {
+    /// One square bouncing around the screen
+    MovingSquare,
+    /// Many small stars drifting sideways
+    StarField,
+    /// A dot that follows the mouse while the left button is held
+    Dot,
+}
+
+impl BloomingMode {
+    const ALL: [BloomingMode; 3] = [BloomingMode::MovingSquare, BloomingMode::StarField, BloomingMode::Dot];
+
+    fn name(self) -> &'static str {
+        match self {
+            BloomingMode::MovingSquare => "Moving square",
+            BloomingMode::StarField => "Star field",
+            BloomingMode::Dot => "Drag dot",
+        }
+    }
+}
+
+const STAR_COUNT: u32 = 200;
+
+impl Default for Blooming {
+    fn default() -> Self {
+        Self {
+            params: Params::new(vec![
+                Param::choice(MODE, "Mode", BloomingMode::ALL.map(BloomingMode::name), 0),
+                Param::nits(NITS, "Object", 1000.0, 1.0, 10000.0),
+                Param::float(SIZE, "Size (screen heights)", 0.05, 0.002, 0.5).logarithmic(),
+                Param::float(SPEED, "Speed (heights/s)", 0.25, 0.01, 4.0).logarithmic(),
+            ]),
+            dot: [0.0, 0.0],
+        }
+    }
+}
+
+impl Blooming {
+    fn mode(&self) -> BloomingMode {
+        BloomingMode::ALL[self.params.choice(MODE)]
+    }
+}
+
+/// Position bouncing between `min` and `max` at `speed` units per second
+fn bounce(time: f64, speed: f32, min: f32, max: f32) -> f32 {
+    let span = max - min;
+    if span <= 0.0 {
+        return min;
+    }
+    let d = (time * speed as f64).rem_euclid(2.0 * span as f64) as f32;
+    if d < span { min + d } else { max - (d - span) }
+}
+
+/// Deterministic pseudo-random value in 0..1
+fn hash(mut x: u32) -> f32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    x as f32 / u32::MAX as f32
+}
+
+impl Page for Blooming {
+    fn name(&self) -> &'static str {
+        "Local Dimming / Blooming"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+
+        let nits = self.params.float(NITS);
+        let speed = self.params.float(SPEED);
+        // Half extents of one object in NDC
+        let half_h = self.params.float(SIZE);
+        let half_w = half_h / VIEWPORT_ASPECT;
+        let time = ctx.clock.time();
+        let value = nits_to_scrgb(nits);
+        let color = [value, value, value, 1.0];
+
+        match self.mode() {
+            BloomingMode::MovingSquare => {
+                // Different horizontal and vertical periods trace the whole screen
+                let x = bounce(time, speed * 2.0 / VIEWPORT_ASPECT, -1.0 + half_w, 1.0 - half_w);
+                let y = bounce(time, speed * 2.0 * 0.618, -1.0 + half_h, 1.0 - half_h);
+                add_quad(&mut vertices, x - half_w, y + half_h, x + half_w, y - half_h, color);
+            }
+            BloomingMode::StarField => {
+                let star_w = half_w * 0.5;
+                let star_h = half_h * 0.5;
+                for i in 0..STAR_COUNT {
+                    // Each star drifts at its own depth and wraps around the screen
+                    let depth = 0.3 + 0.7 * hash(i * 3 + 2);
+                    let drift = (time * (speed * depth * 2.0 / VIEWPORT_ASPECT) as f64).rem_euclid(2.0) as f32;
+                    let x = (hash(i * 3) * 2.0 + drift).rem_euclid(2.0) - 1.0;
+                    let y = hash(i * 3 + 1) * 2.0 - 1.0;
+                    let star = value * depth;
+                    add_quad(
+                        &mut vertices,
+                        x - star_w * depth,
+                        y + star_h * depth,
+                        x + star_w * depth,
+                        y - star_h * depth,
+                        [star, star, star, 1.0],
+                    );
+                }
+            }
+            BloomingMode::Dot => {
+                let canvas = Canvas::new(ctx.width, ctx.height);
+                add_circle(&mut vertices, &canvas, self.dot, half_h * 0.4, color);
+            }
+        }
+
+        let labels = vec![HdrTextLabel {
+            text: format!(
+                "{}  {}nits  1-3: mode  Up/Down: nits  Left/Right: speed  +/-: size",
+                self.mode().name(),
+                format_nits(nits),
+            ),
+            x: -0.95,
+            y: -0.92,
+            nits: 40.0,
+            size: font_size,
+        }];
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        let nits = self.params.float(NITS);
+        let speed = self.params.float(SPEED);
+        let size = self.params.float(SIZE);
+        match key {
+            Key::Character(c) if c == "1" => self.params.set(MODE, 0),
+            Key::Character(c) if c == "2" => self.params.set(MODE, 1),
+            Key::Character(c) if c == "3" => self.params.set(MODE, 2),
+            Key::Named(NamedKey::ArrowUp) => self.params.set(NITS, (nits * 2.0).min(10000.0)),
+            Key::Named(NamedKey::ArrowDown) => self.params.set(NITS, (nits / 2.0).max(1.0)),
+            Key::Named(NamedKey::ArrowRight) => self.params.set(SPEED, (speed * 1.5).min(4.0)),
+            Key::Named(NamedKey::ArrowLeft) => self.params.set(SPEED, (speed / 1.5).max(0.01)),
+            Key::Character(c) if c == "+" || c == "=" => self.params.set(SIZE, (size * 1.25).min(0.5)),
+            Key::Character(c) if c == "-" => self.params.set(SIZE, (size / 1.25).max(0.002)),
+            _ => {}
+        }
+        None
+    }
+
+    fn on_pointer(&mut self, pos: [f32; 2], pressed: bool) {
+        if pressed && self.mode() == BloomingMode::Dot {
+            self.dot = [pos[0].clamp(-1.0, 1.0), pos[1].clamp(-1.0, 1.0)];
+        }
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
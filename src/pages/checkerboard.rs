@@ -0,0 +1,191 @@
+use crate::transfer::{pq, BitDepth, CodeFormat, Range};
+use crate::ui::HdrTextLabel;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageAction, PageOutput, RenderContext, add_quad, format_nits, nits_to_scrgb};
+
+/// 4x4 ANSI checkerboard for intra-scene (simultaneous) contrast
+pub struct AnsiCheckerboard {
+    params: Params,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CheckerLevels {
+    /// Black at 0 and white at the current max brightness
+    Peak,
+    /// Black at 0 and white at a fixed luminance in nits
+    White(f32),
+    /// Both cells at 10-bit full range PQ codes
+    PqCodes { black: u16, white: u16 },
+}
+
+/// Options of `LEVELS`, in `CheckerLevels` order
+const LEVEL_MODES: &[&str] = &["Peak", "White nits", "PQ codes"];
+const LEVELS: ParamId<usize> = ParamId::new("levels");
+const WHITE_NITS: ParamId<f32> = ParamId::new("white_nits");
+const BLACK_CODE: ParamId<i32> = ParamId::new("black_code");
+const WHITE_CODE: ParamId<i32> = ParamId::new("white_code");
+/// Swaps black and white cells so every position can be measured both ways
+const INVERTED: ParamId<bool> = ParamId::new("inverted");
+
+const FORMAT: CodeFormat = CodeFormat::new(BitDepth::Ten, Range::Full);
+const CELLS: usize = 4;
+
+impl Default for AnsiCheckerboard {
+    fn default() -> Self {
+        let max_code = FORMAT.white_code() as i32;
+        Self {
+            params: Params::new(vec![
+                Param::choice(LEVELS, "Levels", LEVEL_MODES, 0),
+                Param::nits(WHITE_NITS, "White", 1000.0, 1.0, 10000.0),
+                Param::int(BLACK_CODE, "Black code", 64, 0, max_code - 1),
+                Param::int(WHITE_CODE, "White code", pq::nits_to_code(1000.0, FORMAT) as i32, 1, max_code),
+                Param::bool(INVERTED, "Inverted", false),
+            ]),
+        }
+    }
+}
+
+impl AnsiCheckerboard {
+    fn levels(&self) -> CheckerLevels {
+        match self.params.choice(LEVELS) {
+            0 => CheckerLevels::Peak,
+            1 => CheckerLevels::White(self.params.float(WHITE_NITS)),
+            _ => {
+                let black = self.params.int(BLACK_CODE) as u16;
+                let white = (self.params.int(WHITE_CODE) as u16).max(black + 1);
+                CheckerLevels::PqCodes { black, white }
+            }
+        }
+    }
+
+    /// Black and white cell luminance in nits
+    fn cell_nits(&self, max_brightness_nits: f32) -> (f32, f32) {
+        match self.levels() {
+            CheckerLevels::Peak => (0.0, max_brightness_nits),
+            CheckerLevels::White(nits) => (0.0, nits),
+            CheckerLevels::PqCodes { black, white } => {
+                (pq::code_to_nits(black, FORMAT), pq::code_to_nits(white, FORMAT))
+            }
+        }
+    }
+}
+
+impl Page for AnsiCheckerboard {
+    fn name(&self) -> &'static str {
+        "ANSI Checkerboard"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+
+        let (black_nits, white_nits) = self.cell_nits(ctx.max_brightness_nits);
+        let black = nits_to_scrgb(black_nits);
+        let white = nits_to_scrgb(white_nits);
+        let inverted = self.params.bool(INVERTED);
+        let cell_w = 2.0 / CELLS as f32;
+        let cell_h = 2.0 / CELLS as f32;
+
+        for row in 0..CELLS {
+            for col in 0..CELLS {
+                let is_white = ((row + col) % 2 == 0) != inverted;
+                let value = if is_white { white } else { black };
+                let x0 = -1.0 + col as f32 * cell_w;
+                let y0 = 1.0 - row as f32 * cell_h;
+                add_quad(&mut vertices, x0, y0, x0 + cell_w, y0 - cell_h, [value, value, value, 1.0]);
+            }
+        }
+
+        let levels = match self.levels() {
+            CheckerLevels::PqCodes { black, white } => format!(
+                "black PQ:{} {}nits  white PQ:{} {}nits",
+                black,
+                format_nits(black_nits),
+                white,
+                format_nits(white_nits),
+            ),
+            CheckerLevels::Peak | CheckerLevels::White(_) => {
+                format!("black 0nits  white {}nits", format_nits(white_nits))
+            }
+        };
+        let contrast = if black_nits > 0.0 {
+            format!("  ideal {:.0}:1", white_nits / black_nits)
+        } else {
+            String::new()
+        };
+
+        // The label sits inside a black cell in the bottom row
+        let label_col = if inverted { 1.0 } else { 0.0 };
+        let labels = vec![
+            HdrTextLabel {
+                text: format!("{}{}", levels, contrast),
+                x: -1.0 + label_col * cell_w + 0.01,
+                y: -0.88,
+                nits: 40.0,
+                size: font_size,
+            },
+            HdrTextLabel {
+                text: "I: invert  P: PQ codes  Arrows: levels".to_string(),
+                x: -1.0 + label_col * cell_w + 0.01,
+                y: -0.94,
+                nits: 40.0,
+                size: font_size,
+            },
+        ];
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, modifiers: ModifiersState) -> Option<PageAction> {
+        let step = if modifiers.shift_key() { 10 } else { 1 };
+        match key {
+            Key::Character(c) if c.eq_ignore_ascii_case("i") => {
+                let inverted = self.params.bool(INVERTED);
+                self.params.set(INVERTED, !inverted);
+            }
+            Key::Character(c) if c.eq_ignore_ascii_case("p") => {
+                let mode = if matches!(self.levels(), CheckerLevels::PqCodes { .. }) { 0 } else { 2 };
+                self.params.set(LEVELS, mode);
+            }
+            Key::Named(NamedKey::ArrowUp) | Key::Named(NamedKey::ArrowDown) => {
+                let up = matches!(key, Key::Named(NamedKey::ArrowUp));
+                match self.levels() {
+                    CheckerLevels::PqCodes { black, white } => {
+                        let white = if up {
+                            (white + step).min(FORMAT.white_code())
+                        } else {
+                            white.saturating_sub(step).max(black + 1)
+                        };
+                        self.params.set(WHITE_CODE, white as i32);
+                    }
+                    CheckerLevels::Peak => {
+                        self.params.set(LEVELS, 1);
+                        self.params.set(WHITE_NITS, if up { 10000.0 } else { 500.0 });
+                    }
+                    CheckerLevels::White(nits) => {
+                        self.params.set(WHITE_NITS, if up { (nits * 2.0).min(10000.0) } else { (nits / 2.0).max(1.0) });
+                    }
+                }
+            }
+            Key::Named(NamedKey::ArrowRight) | Key::Named(NamedKey::ArrowLeft) => {
+                if let CheckerLevels::PqCodes { black, white } = self.levels() {
+                    let black = if matches!(key, Key::Named(NamedKey::ArrowRight)) {
+                        (black + step).min(white - 1)
+                    } else {
+                        black.saturating_sub(step)
+                    };
+                    self.params.set(BLACK_CODE, black as i32);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
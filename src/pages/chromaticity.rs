@@ -0,0 +1,271 @@
+use crate::color::{self, BT2020, BT709, Color, ColorSpace, D65, DISPLAY_P3, SPECTRAL_LOCUS};
+use crate::ui::HdrTextLabel;
+use glam::{Mat3, Vec3};
+use super::geometry::{Canvas, add_pixel_line, add_ring};
+use super::{Page, PageOutput, RenderContext, VIEWPORT_ASPECT, add_triangle, nits_to_scrgb};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Diagram {
+    /// CIE 1931 xy
+    Xy,
+    /// CIE 1976 u'v'
+    UvPrime,
+}
+
+/// Chromaticity diagram with the spectral locus filled in and the common
+/// HDR gamuts drawn on top
+pub struct Chromaticity {
+    pub diagram: Diagram,
+}
+
+/// Rings each fan triangle is split into, so interpolated vertex colors stay
+/// close to the chromaticity they are drawn at
+const FILL_RINGS: usize = 8;
+/// Segments the purple line between 380nm and 700nm is split into
+const PURPLE_SEGMENTS: usize = 8;
+/// Brightest channel of the fill, as a fraction of max brightness
+const FILL_LEVEL: f32 = 0.5;
+
+/// Line width in pixels at 1080p
+const LINE_PIXELS: f32 = 2.0;
+
+/// Gamut triangles with their outline brightness in nits
+const GAMUTS: [(&str, &ColorSpace, f32); 3] = [
+    ("BT.709", &BT709, 200.0),
+    ("P3", &DISPLAY_P3, 120.0),
+    ("BT.2020", &BT2020, 60.0),
+];
+
+impl Diagram {
+    fn project(self, xy: [f32; 2]) -> [f32; 2] {
+        match self {
+            Diagram::Xy => xy,
+            Diagram::UvPrime => color::xy_to_uv_prime(xy),
+        }
+    }
+
+    /// Upper end of each plotted axis
+    fn extent(self) -> [f32; 2] {
+        match self {
+            Diagram::Xy => [0.8, 0.9],
+            Diagram::UvPrime => [0.65, 0.65],
+        }
+    }
+
+    fn axis_names(self) -> [&'static str; 2] {
+        match self {
+            Diagram::Xy => ["x", "y"],
+            Diagram::UvPrime => ["u'", "v'"],
+        }
+    }
+}
+
+/// Maps diagram coordinates to NDC with equal scale on both axes
+struct Plot {
+    diagram: Diagram,
+    origin: [f32; 2],
+    scale: [f32; 2],
+}
+
+impl Plot {
+    fn new(diagram: Diagram, margin: f32) -> Self {
+        let extent = diagram.extent();
+        let available = 2.0 - 2.0 * margin;
+
+        // Work in units of viewport height so both axes get the same scale
+        let scale = (available * 0.5 * VIEWPORT_ASPECT / extent[0]).min(available * 0.5 / extent[1]);
+        let scale = [scale * 2.0 / VIEWPORT_ASPECT, scale * 2.0];
+        let origin = [-extent[0] * scale[0] * 0.5, -extent[1] * scale[1] * 0.5];
+
+        Self { diagram, origin, scale }
+    }
+
+    /// Diagram coordinates to NDC
+    fn to_ndc(&self, p: [f32; 2]) -> [f32; 2] {
+        [self.origin[0] + p[0] * self.scale[0], self.origin[1] + p[1] * self.scale[1]]
+    }
+
+    /// CIE 1931 xy to NDC
+    fn xy_to_ndc(&self, xy: [f32; 2]) -> [f32; 2] {
+        self.to_ndc(self.diagram.project(xy))
+    }
+}
+
+/// Most saturated displayable color at an xy chromaticity: out-of-gamut
+/// chromaticities are pulled onto the BT.2020 boundary
+fn fill_color(xyz_to_bt2020: &Mat3, xy: [f32; 2], channel_nits: f32) -> [f32; 4] {
+    let rgb = (*xyz_to_bt2020 * color::xy_to_xyz(xy)).max(Vec3::ZERO);
+    let peak = rgb.max_element();
+    if peak <= 0.0 {
+        return [0.0, 0.0, 0.0, 1.0];
+    }
+    Color::new(&BT2020, (rgb / peak * channel_nits).to_array()).to_scrgb()
+}
+
+fn lerp(a: [f32; 2], b: [f32; 2], t: f32) -> [f32; 2] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+}
+
+impl Page for Chromaticity {
+    fn name(&self) -> &'static str {
+        match self.diagram {
+            Diagram::Xy => "CIE 1931 xy Chromaticity",
+            Diagram::UvPrime => "CIE 1976 u'v' Chromaticity",
+        }
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+        let canvas = Canvas::new(ctx.width, ctx.height);
+        let line = (LINE_PIXELS * scale).max(1.0);
+
+        let plot = Plot::new(self.diagram, 0.1);
+        let xyz_to_bt2020 = BT2020.xyz_to_rgb();
+        let channel_nits = ctx.max_brightness_nits * FILL_LEVEL;
+
+        // Boundary of the visible region: the spectral locus closed by the purple line
+        let mut boundary: Vec<[f32; 2]> = SPECTRAL_LOCUS.to_vec();
+        let red_end = SPECTRAL_LOCUS[SPECTRAL_LOCUS.len() - 1];
+        let blue_end = SPECTRAL_LOCUS[0];
+        for seg in 1..PURPLE_SEGMENTS {
+            boundary.push(lerp(red_end, blue_end, seg as f32 / PURPLE_SEGMENTS as f32));
+        }
+
+        // Fill as a fan around the white point, split into rings
+        for i in 0..boundary.len() {
+            let a = boundary[i];
+            let b = boundary[(i + 1) % boundary.len()];
+            for ring in 0..FILL_RINGS {
+                let t0 = ring as f32 / FILL_RINGS as f32;
+                let t1 = (ring + 1) as f32 / FILL_RINGS as f32;
+                let corners = [lerp(D65, a, t0), lerp(D65, a, t1), lerp(D65, b, t1), lerp(D65, b, t0)];
+                let positions = corners.map(|xy| plot.xy_to_ndc(xy));
+                let colors = corners.map(|xy| fill_color(&xyz_to_bt2020, xy, channel_nits));
+
+                add_triangle(
+                    &mut vertices,
+                    [positions[0], positions[1], positions[2]],
+                    [colors[0], colors[1], colors[2]],
+                );
+                if ring > 0 {
+                    add_triangle(
+                        &mut vertices,
+                        [positions[0], positions[2], positions[3]],
+                        [colors[0], colors[2], colors[3]],
+                    );
+                }
+            }
+        }
+
+        // Locus outline
+        let outline = [nits_to_scrgb(40.0), nits_to_scrgb(40.0), nits_to_scrgb(40.0), 1.0];
+        for i in 0..SPECTRAL_LOCUS.len() {
+            let a = plot.xy_to_ndc(SPECTRAL_LOCUS[i]);
+            let b = plot.xy_to_ndc(SPECTRAL_LOCUS[(i + 1) % SPECTRAL_LOCUS.len()]);
+            add_pixel_line(&mut vertices, &canvas, a, b, line, outline);
+        }
+
+        // Wavelength labels, pushed outward from the white point
+        let white = plot.xy_to_ndc(D65);
+        for (i, &xy) in SPECTRAL_LOCUS.iter().enumerate() {
+            let nm = 380 + i * 5;
+            if !(460..=620).contains(&nm) || nm % 20 != 0 {
+                continue;
+            }
+            let p = plot.xy_to_ndc(xy);
+            let (dx, dy) = (p[0] - white[0], p[1] - white[1]);
+            let length = (dx * dx + dy * dy).sqrt().max(1e-6);
+            labels.push(HdrTextLabel {
+                text: format!("{}", nm),
+                x: p[0] + dx / length * 0.04 - 0.02,
+                y: p[1] + dy / length * 0.06 + 0.02,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        // Gamut triangles
+        for (name, space, nits) in GAMUTS {
+            let value = nits_to_scrgb(nits);
+            let line_color = [value, value, value, 1.0];
+            let corners = [space.red, space.green, space.blue].map(|xy| plot.xy_to_ndc(xy));
+            for i in 0..3 {
+                add_pixel_line(&mut vertices, &canvas, corners[i], corners[(i + 1) % 3], line, line_color);
+            }
+            labels.push(HdrTextLabel {
+                text: name.to_string(),
+                x: corners[0][0] + 0.01,
+                y: corners[0][1] + 0.01,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        // D65 marker: a ring around a crosshair
+        let marker = 0.035;
+        let value = nits_to_scrgb(200.0);
+        let marker_color = [value, value, value, 1.0];
+        let ring_width = line * 2.0 / canvas.height;
+        add_ring(&mut vertices, &canvas, white, (marker * 0.6 - ring_width, marker * 0.6), marker_color);
+        let left = canvas.polar(white, marker, std::f32::consts::PI);
+        let right = canvas.polar(white, marker, 0.0);
+        add_pixel_line(&mut vertices, &canvas, left, right, line, marker_color);
+        add_pixel_line(&mut vertices, &canvas, [white[0], white[1] - marker], [white[0], white[1] + marker], line, marker_color);
+        labels.push(HdrTextLabel {
+            text: "D65".to_string(),
+            x: right[0],
+            y: white[1] - 0.01,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        // Axes with a tick label every 0.1
+        let extent = self.diagram.extent();
+        let origin = plot.to_ndc([0.0, 0.0]);
+        add_pixel_line(&mut vertices, &canvas, origin, plot.to_ndc([extent[0], 0.0]), line, outline);
+        add_pixel_line(&mut vertices, &canvas, origin, plot.to_ndc([0.0, extent[1]]), line, outline);
+        let [x_name, y_name] = self.diagram.axis_names();
+        for tick in 1..=(extent[0] * 10.0).round() as usize {
+            let p = plot.to_ndc([tick as f32 * 0.1, 0.0]);
+            labels.push(HdrTextLabel {
+                text: format!("{:.1}", tick as f32 * 0.1),
+                x: p[0] - 0.015,
+                y: p[1] - 0.01,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+        for tick in 1..=(extent[1] * 10.0).round() as usize {
+            let p = plot.to_ndc([0.0, tick as f32 * 0.1]);
+            labels.push(HdrTextLabel {
+                text: format!("{:.1}", tick as f32 * 0.1),
+                x: p[0] - 0.05,
+                y: p[1] + 0.02,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+        let x_end = plot.to_ndc([extent[0], 0.0]);
+        let y_end = plot.to_ndc([0.0, extent[1]]);
+        labels.push(HdrTextLabel {
+            text: x_name.to_string(),
+            x: x_end[0] + 0.02,
+            y: x_end[1] + 0.02,
+            nits: 40.0,
+            size: font_size,
+        });
+        labels.push(HdrTextLabel {
+            text: y_name.to_string(),
+            x: y_end[0] - 0.01,
+            y: y_end[1] + 0.06,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        PageOutput { vertices, labels }
+    }
+}
@@ -1,27 +1,48 @@
-use super::{Page, PageOutput, add_gradient_quad_h};
+use super::geometry::add_gradient_quad_h;
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageOutput, RenderContext};
 
-pub struct ColorRamps;
+pub struct ColorRamps {
+    params: Params,
+}
+
+const RAMPS: [ParamId<[f32; 3]>; 6] = [
+    ParamId::new("ramp1"),
+    ParamId::new("ramp2"),
+    ParamId::new("ramp3"),
+    ParamId::new("ramp4"),
+    ParamId::new("ramp5"),
+    ParamId::new("ramp6"),
+];
+
+impl Default for ColorRamps {
+    fn default() -> Self {
+        Self {
+            params: Params::new(vec![
+                Param::color(RAMPS[0], "Ramp 1", [1.0, 0.0, 0.0]),
+                Param::color(RAMPS[1], "Ramp 2", [0.0, 1.0, 0.0]),
+                Param::color(RAMPS[2], "Ramp 3", [0.0, 0.0, 1.0]),
+                Param::color(RAMPS[3], "Ramp 4", [1.0, 1.0, 0.0]),
+                Param::color(RAMPS[4], "Ramp 5", [1.0, 0.0, 1.0]),
+                Param::color(RAMPS[5], "Ramp 6", [0.0, 1.0, 1.0]),
+            ]),
+        }
+    }
+}
 
 impl Page for ColorRamps {
     fn name(&self) -> &'static str {
         "Color Ramps"
     }
 
-    fn render(&self, _width: u32, _height: u32, max_brightness_nits: f32, _time: f32) -> PageOutput {
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
         let mut vertices = Vec::new();
 
-        let colors: [[f32; 3]; 6] = [
-            [1.0, 0.0, 0.0],
-            [0.0, 1.0, 0.0],
-            [0.0, 0.0, 1.0],
-            [1.0, 1.0, 0.0],
-            [1.0, 0.0, 1.0],
-            [0.0, 1.0, 1.0],
-        ];
+        let colors = RAMPS.map(|id| self.params.color(id));
 
         let bar_count = colors.len();
         let bar_height = 2.0 / bar_count as f32;
-        let max_scrgb = max_brightness_nits / 80.0;
+        let max_scrgb = ctx.max_brightness_nits / 80.0;
         let segments = 32;
 
         for (i, base_color) in colors.iter().enumerate() {
@@ -47,6 +68,10 @@ impl Page for ColorRamps {
 
         PageOutput { vertices, labels: Vec::new() }
     }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
 }
 
 fn compute_ramp_color(base: &[f32; 3], t: f32, max_scrgb: f32) -> [f32; 4] {
@@ -0,0 +1,135 @@
+use crate::color::{chromatic_adaptation, lab_to_xyz, Color, BT709, D50, D65};
+use crate::ui::HdrTextLabel;
+use winit::keyboard::{Key, ModifiersState};
+use super::layout::{CellShape, GridLayout, Rect};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageAction, PageOutput, RenderContext, add_quad, format_nits};
+
+/// ColorChecker Classic patches with a 100% reflector at a reference white
+pub struct ColorChecker {
+    params: Params,
+}
+
+const REFERENCE: ParamId<usize> = ParamId::new("reference");
+
+/// Choices for the 100% reflector: the HDR reference white of ITU-R BT.2408,
+/// or the app's paper white setting
+const REFERENCES: &[&str] = &["BT.2408 (203 nits)", "Paper white"];
+
+impl Default for ColorChecker {
+    fn default() -> Self {
+        Self { params: Params::new(vec![Param::choice(REFERENCE, "Reference white", REFERENCES, 0)]) }
+    }
+}
+
+/// X-Rite CIELAB (D50) values, post-2014 formulation, in chart order
+const PATCHES: [(&str, [f32; 3]); 24] = [
+    ("Dark Skin", [37.54, 14.37, 14.92]),
+    ("Light Skin", [64.66, 19.27, 17.50]),
+    ("Blue Sky", [49.32, -3.82, -22.54]),
+    ("Foliage", [43.46, -12.74, 22.72]),
+    ("Blue Flower", [54.94, 9.61, -24.79]),
+    ("Bluish Green", [70.48, -32.26, -0.37]),
+    ("Orange", [62.73, 35.83, 56.50]),
+    ("Purplish Blue", [39.43, 10.75, -45.17]),
+    ("Moderate Red", [50.57, 48.64, 16.67]),
+    ("Purple", [30.10, 22.54, -20.87]),
+    ("Yellow Green", [71.77, -24.13, 58.19]),
+    ("Orange Yellow", [71.51, 18.24, 67.37]),
+    ("Blue", [28.37, 15.42, -49.80]),
+    ("Green", [54.38, -39.72, 32.27]),
+    ("Red", [42.43, 51.05, 28.62]),
+    ("Yellow", [81.80, 2.67, 80.41]),
+    ("Magenta", [50.63, 51.28, -14.12]),
+    ("Cyan", [49.57, -29.71, -28.32]),
+    ("White 9.5", [95.19, -1.03, 2.93]),
+    ("Neutral 8", [81.29, -0.57, 0.44]),
+    ("Neutral 6.5", [66.89, -0.75, -0.06]),
+    ("Neutral 5", [50.76, -0.13, 0.14]),
+    ("Neutral 3.5", [35.63, -0.46, -0.48]),
+    ("Black 2", [20.64, 0.07, -0.46]),
+];
+
+const COLS: usize = 6;
+
+impl ColorChecker {
+    fn reference_nits(&self, paper_white_nits: f32) -> f32 {
+        match self.params.choice(REFERENCE) {
+            0 => 203.0,
+            _ => paper_white_nits,
+        }
+    }
+}
+
+impl Page for ColorChecker {
+    fn name(&self) -> &'static str {
+        "ColorChecker"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+
+        let margin = 0.08f32;
+        // Leave room for the footer, and keep patches wide enough for their names
+        let layout = GridLayout {
+            bounds: Rect { y1: -0.95, ..Rect::FULL },
+            cols: Some(COLS),
+            shape: CellShape::Aspect(1.5),
+            ..GridLayout::default()
+        };
+        let cells = layout.cells(PATCHES.len(), ctx.width, ctx.height);
+
+        let reference_nits = self.reference_nits(ctx.paper_white_nits);
+        let adapt = chromatic_adaptation(D50, D65);
+        let to_rgb = BT709.xyz_to_rgb();
+
+        for (cell, &(name, lab)) in cells.iter().zip(&PATCHES) {
+            let xyz = adapt * lab_to_xyz(lab, D50) * reference_nits;
+            let color = Color::new(&BT709, (to_rgb * xyz).into());
+
+            add_quad(&mut vertices, cell.rect.x0, cell.rect.y0, cell.rect.x1, cell.rect.y1, color);
+
+            labels.push(HdrTextLabel {
+                text: format!("{} {}nits", name, format_nits(xyz.y)),
+                x: cell.label.x0,
+                y: cell.label.y0,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        let reference = match self.params.choice(REFERENCE) {
+            0 => "BT.2408",
+            _ => "paper white",
+        };
+        labels.push(HdrTextLabel {
+            text: format!(
+                "100% reflectance at {}nits ({})  R: toggle reference",
+                format_nits(reference_nits),
+                reference
+            ),
+            x: -1.0 + margin,
+            y: -1.0 + margin,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        match key {
+            Key::Character(c) if c.eq_ignore_ascii_case("r") => self.params.next_choice(REFERENCE),
+            _ => {}
+        }
+        None
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
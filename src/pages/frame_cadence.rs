@@ -0,0 +1,104 @@
+use crate::ui::HdrTextLabel;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::layout::{GridLayout, Rect, Spacing};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageAction, PageOutput, RenderContext, add_quad, format_nits, nits_to_scrgb};
+
+/// A strip of cells with exactly one lit per presented frame. In a long
+/// camera exposure or slow-motion video, a skipped frame shows as a cell that
+/// never lights and a repeated frame as one lit twice as long.
+pub struct FrameCadence {
+    params: Params,
+}
+
+const CELLS: ParamId<i32> = ParamId::new("cells");
+const NITS: ParamId<f32> = ParamId::new("nits");
+
+const DIM_NITS: f32 = 2.0;
+
+impl Default for FrameCadence {
+    fn default() -> Self {
+        Self {
+            params: Params::new(vec![
+                Param::int(CELLS, "Cells", 12, 2, 60),
+                Param::nits(NITS, "Lit cell", 200.0, 10.0, 1000.0),
+            ]),
+        }
+    }
+}
+
+impl Page for FrameCadence {
+    fn name(&self) -> &'static str {
+        "Frame Cadence"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+
+        let margin = 0.08f32;
+        let layout = GridLayout {
+            bounds: Rect { y0: 0.28, y1: -0.33, ..Rect::FULL },
+            rows: Some(1),
+            gap: Spacing::Pixels(4.0),
+            ..GridLayout::default()
+        };
+        // Count presents, not clock frames: pausing or stepping the clock
+        // must not stop the strip
+        let frame = ctx.presents;
+        let cells = self.params.int(CELLS) as u32;
+        let nits = self.params.float(NITS);
+        let lit = (frame % cells as u64) as u32;
+
+        let bright = nits_to_scrgb(nits);
+        let dim = nits_to_scrgb(DIM_NITS);
+        for (index, cell) in layout.cells(cells as usize, ctx.width, ctx.height).iter().enumerate() {
+            let value = if index as u32 == lit { bright } else { dim };
+            add_quad(&mut vertices, cell.rect.x0, cell.rect.y0, cell.rect.x1, cell.rect.y1, [value, value, value, 1.0]);
+            labels.push(HdrTextLabel {
+                text: index.to_string(),
+                x: cell.label.x0,
+                y: cell.label.y0,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        labels.push(HdrTextLabel {
+            text: format!("Frame {}", frame),
+            x: -1.0 + margin,
+            y: 0.8,
+            nits: 40.0,
+            size: font_size * 5.0,
+        });
+        labels.push(HdrTextLabel {
+            text: format!(
+                "One cell lit per presented frame, {}nits  Up/Down: cells",
+                format_nits(nits)
+            ),
+            x: -1.0 + margin,
+            y: -0.9,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        let cells = self.params.int(CELLS);
+        match key {
+            Key::Named(NamedKey::ArrowUp) => self.params.set(CELLS, (cells + 1).min(60)),
+            Key::Named(NamedKey::ArrowDown) => self.params.set(CELLS, (cells - 1).max(2)),
+            _ => {}
+        }
+        None
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
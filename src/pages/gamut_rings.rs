@@ -0,0 +1,82 @@
+use crate::color::{BT2020, BT709, Color, ColorSpace, DISPLAY_P3};
+use crate::ui::HdrTextLabel;
+use super::layout::{CellShape, GridLayout};
+use super::{Page, PageOutput, RenderContext, add_quad, format_nits};
+
+/// Nested patches of each primary and secondary in BT.2020, P3 and BT.709.
+/// All rings share one luminance, so where the display's gamut ends the
+/// outer rings become indistinguishable from the ones inside them.
+pub struct GamutRings;
+
+const HUES: [(&str, [f32; 3]); 6] = [
+    ("Red", [1.0, 0.0, 0.0]),
+    ("Green", [0.0, 1.0, 0.0]),
+    ("Blue", [0.0, 0.0, 1.0]),
+    ("Yellow", [1.0, 1.0, 0.0]),
+    ("Magenta", [1.0, 0.0, 1.0]),
+    ("Cyan", [0.0, 1.0, 1.0]),
+];
+
+/// Outermost ring first
+const RINGS: [(&str, &ColorSpace); 3] = [
+    ("2020", &BT2020),
+    ("P3", &DISPLAY_P3),
+    ("709", &BT709),
+];
+
+impl Page for GamutRings {
+    fn name(&self) -> &'static str {
+        "Gamut Rings (2020 / P3 / 709)"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+
+        let layout = GridLayout { cols: Some(3), shape: CellShape::Square, ..GridLayout::default() };
+        let cells = layout.cells(HUES.len(), ctx.width, ctx.height);
+
+        for (cell, (hue_name, rgb)) in cells.iter().zip(&HUES) {
+            // Brightest luminance every space can reach for this hue
+            let nits = RINGS
+                .iter()
+                .map(|(_, space)| space.luminance(*rgb))
+                .fold(f32::MAX, f32::min)
+                * ctx.max_brightness_nits;
+
+            let rect = cell.rect;
+            for (ring, (ring_name, space)) in RINGS.iter().enumerate() {
+                let inset_x = ring as f32 * rect.width() / 6.0;
+                let inset_y = ring as f32 * rect.height() / 6.0;
+                let rx0 = rect.x0 + inset_x;
+                let ry0 = rect.y0 - inset_y;
+                let rx1 = rect.x1 - inset_x;
+                let ry1 = rect.y1 + inset_y;
+
+                let color = Color::with_luminance(space, *rgb, nits);
+                add_quad(&mut vertices, rx0, ry0, rx1, ry1, color);
+
+                labels.push(HdrTextLabel {
+                    text: ring_name.to_string(),
+                    x: rx0 + 0.01,
+                    y: ry0 - 0.01,
+                    nits: 40.0,
+                    size: font_size,
+                });
+            }
+
+            labels.push(HdrTextLabel {
+                text: format!("{} {}nits", hue_name, format_nits(nits)),
+                x: cell.label.x0,
+                y: cell.label.y0,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        PageOutput { vertices, labels }
+    }
+}
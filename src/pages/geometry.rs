@@ -0,0 +1,225 @@
+//! Tessellation of shapes into `Vertex` triangles. Round shapes get enough
+//! segments to look smooth at their size on screen, and line widths can be
+//! given in pixels.
+
+use crate::dx12::{letterbox_viewport, Vertex};
+use std::f32::consts::TAU;
+use super::add_triangle;
+
+/// Largest distance in pixels between a circle and its polygon
+const CIRCLE_TOLERANCE: f32 = 0.25;
+const MIN_CIRCLE_SEGMENTS: usize = 12;
+const MAX_CIRCLE_SEGMENTS: usize = 512;
+
+/// The page viewport in pixels, for converting sizes between NDC and pixels
+#[derive(Clone, Copy, Debug)]
+pub struct Canvas {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Canvas {
+    /// Viewport of a page rendered into a `width` x `height` window
+    pub fn new(width: u32, height: u32) -> Self {
+        let (width, height, _, _) = letterbox_viewport(width, height);
+        Self { width, height }
+    }
+
+    /// NDC to pixels from the top-left corner
+    pub fn to_pixels(self, p: [f32; 2]) -> [f32; 2] {
+        [(p[0] + 1.0) * 0.5 * self.width, (1.0 - p[1]) * 0.5 * self.height]
+    }
+
+    pub fn to_ndc(self, p: [f32; 2]) -> [f32; 2] {
+        [p[0] / self.width * 2.0 - 1.0, 1.0 - p[1] / self.height * 2.0]
+    }
+
+    /// Point at `angle` radians on a circle that is round on screen. `radius`
+    /// is in vertical NDC units, so 1.0 is half the viewport height.
+    pub fn polar(self, center: [f32; 2], radius: f32, angle: f32) -> [f32; 2] {
+        let x_radius = radius * self.height / self.width;
+        [center[0] + x_radius * angle.cos(), center[1] + radius * angle.sin()]
+    }
+
+    /// Segments for a full circle of `radius` (vertical NDC) to stay within
+    /// a fraction of a pixel of the true circle
+    pub fn circle_segments(self, radius: f32) -> usize {
+        let pixels = radius.abs() * 0.5 * self.height;
+        // Sagitta of a chord: r * (1 - cos(pi / n)) ~= r * pi^2 / (2 n^2)
+        let segments = std::f32::consts::PI * (pixels / (2.0 * CIRCLE_TOLERANCE)).sqrt();
+        (segments.ceil() as usize).clamp(MIN_CIRCLE_SEGMENTS, MAX_CIRCLE_SEGMENTS)
+    }
+}
+
+/// Quad blending from `left_color` at x0 to `right_color` at x1
+pub fn add_gradient_quad_h(
+    vertices: &mut Vec<Vertex>,
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    left_color: impl Into<[f32; 4]>,
+    right_color: impl Into<[f32; 4]>,
+) {
+    let (left, right) = (left_color.into(), right_color.into());
+    add_triangle(vertices, [[x0, y0], [x0, y1], [x1, y1]], [left, left, right]);
+    add_triangle(vertices, [[x0, y0], [x1, y1], [x1, y0]], [left, right, right]);
+}
+
+/// Disc blending from `inner_color` at the center to `outer_color` at the rim
+pub fn add_radial_gradient(
+    vertices: &mut Vec<Vertex>,
+    canvas: &Canvas,
+    center: [f32; 2],
+    radius: f32,
+    inner_color: impl Into<[f32; 4]>,
+    outer_color: impl Into<[f32; 4]>,
+) {
+    let (inner, outer) = (inner_color.into(), outer_color.into());
+    let segments = canvas.circle_segments(radius);
+    for segment in 0..segments {
+        let a0 = segment as f32 / segments as f32 * TAU;
+        let a1 = (segment + 1) as f32 / segments as f32 * TAU;
+        let rim = [canvas.polar(center, radius, a0), canvas.polar(center, radius, a1)];
+        add_triangle(vertices, [center, rim[0], rim[1]], [inner, outer, outer]);
+    }
+}
+
+pub fn add_circle(vertices: &mut Vec<Vertex>, canvas: &Canvas, center: [f32; 2], radius: f32, color: impl Into<[f32; 4]>) {
+    let color = color.into();
+    add_radial_gradient(vertices, canvas, center, radius, color, color);
+}
+
+/// Annulus between two radii
+pub fn add_ring(
+    vertices: &mut Vec<Vertex>,
+    canvas: &Canvas,
+    center: [f32; 2],
+    radii: (f32, f32),
+    color: impl Into<[f32; 4]>,
+) {
+    let color = color.into();
+    let segments = canvas.circle_segments(radii.0.max(radii.1));
+    for segment in 0..segments {
+        let a0 = segment as f32 / segments as f32 * TAU;
+        let a1 = (segment + 1) as f32 / segments as f32 * TAU;
+        add_ring_sector(vertices, canvas, center, radii, (a0, a1), (color, color));
+    }
+}
+
+/// Part of an annulus between two angles, with a color per angle. Callers
+/// split long arcs themselves, as the sector is a single quad.
+pub fn add_ring_sector(
+    vertices: &mut Vec<Vertex>,
+    canvas: &Canvas,
+    center: [f32; 2],
+    radii: (f32, f32),
+    angles: (f32, f32),
+    colors: ([f32; 4], [f32; 4]),
+) {
+    let a = canvas.polar(center, radii.0, angles.0);
+    let b = canvas.polar(center, radii.1, angles.0);
+    let c = canvas.polar(center, radii.1, angles.1);
+    let d = canvas.polar(center, radii.0, angles.1);
+    add_triangle(vertices, [a, b, c], [colors.0, colors.0, colors.1]);
+    add_triangle(vertices, [a, c, d], [colors.0, colors.1, colors.1]);
+}
+
+/// Line segment `width` pixels wide whatever its direction. Horizontal and
+/// vertical lines are snapped to the pixel grid, so a whole-pixel width
+/// covers exactly that many rows or columns.
+pub fn add_pixel_line(
+    vertices: &mut Vec<Vertex>,
+    canvas: &Canvas,
+    from: [f32; 2],
+    to: [f32; 2],
+    width: f32,
+    color: impl Into<[f32; 4]>,
+) {
+    let color = color.into();
+    let (a, b) = (canvas.to_pixels(from), canvas.to_pixels(to));
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length == 0.0 {
+        return;
+    }
+    let nx = -dy / length * width * 0.5;
+    let ny = dx / length * width * 0.5;
+
+    let mut corners = [[a[0] + nx, a[1] + ny], [a[0] - nx, a[1] - ny], [b[0] - nx, b[1] - ny], [b[0] + nx, b[1] + ny]];
+    if dx == 0.0 || dy == 0.0 {
+        for corner in &mut corners {
+            *corner = corner.map(f32::round);
+        }
+    }
+    let [a, b, c, d] = corners.map(|p| canvas.to_ndc(p));
+    add_triangle(vertices, [a, b, c], [color; 3]);
+    add_triangle(vertices, [a, c, d], [color; 3]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANVAS: Canvas = Canvas { width: 1920.0, height: 1080.0 };
+
+    fn cross(a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> f32 {
+        (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+    }
+
+    #[test]
+    fn circles_get_more_segments_as_they_grow() {
+        let small = CANVAS.circle_segments(0.01);
+        let large = CANVAS.circle_segments(0.9);
+        assert_eq!(small, MIN_CIRCLE_SEGMENTS);
+        assert!(large > small && large < MAX_CIRCLE_SEGMENTS);
+
+        // The polygon stays within tolerance of the circle
+        let radius = 0.9 * 0.5 * CANVAS.height;
+        let sagitta = radius * (1.0 - (std::f32::consts::PI / large as f32).cos());
+        assert!(sagitta <= CIRCLE_TOLERANCE);
+
+        let mut vertices = Vec::new();
+        add_ring(&mut vertices, &CANVAS, [0.0, 0.0], (0.5, 0.9), [1.0; 4]);
+        assert_eq!(vertices.len(), large * 6);
+    }
+
+    #[test]
+    fn circles_are_round_in_pixels() {
+        let right = CANVAS.to_pixels(CANVAS.polar([0.0, 0.0], 0.5, 0.0));
+        let top = CANVAS.to_pixels(CANVAS.polar([0.0, 0.0], 0.5, TAU / 4.0));
+        let center = CANVAS.to_pixels([0.0, 0.0]);
+        assert!((right[0] - center[0] - (center[1] - top[1])).abs() < 1e-3);
+    }
+
+    #[test]
+    fn canvas_is_whole_pixels() {
+        // 1366 / 768 is wider than 16:9 by a fraction of a pixel
+        for (width, height) in [(1366, 768), (1920, 1200), (1000, 1000)] {
+            let canvas = Canvas::new(width, height);
+            assert_eq!(canvas.width, canvas.width.round(), "{width}x{height}");
+            assert_eq!(canvas.height, canvas.height.round(), "{width}x{height}");
+        }
+    }
+
+    #[test]
+    fn pixel_lines_have_exact_width() {
+        let mut vertices = Vec::new();
+        add_pixel_line(&mut vertices, &CANVAS, [-0.5, 0.1], [0.5, 0.1], 1.0, [1.0; 4]);
+        let ys: Vec<f32> = vertices.iter().map(|v| CANVAS.to_pixels(v.position)[1]).collect();
+        let top = ys.iter().copied().fold(f32::MAX, f32::min);
+        let bottom = ys.iter().copied().fold(f32::MIN, f32::max);
+        assert!((bottom - top - 1.0).abs() < 1e-3);
+        assert!((top - top.round()).abs() < 1e-3);
+
+        // Diagonal: area is length times width in pixels
+        let mut vertices = Vec::new();
+        let (from, to) = ([-0.5, -0.5], [0.5, 0.5]);
+        add_pixel_line(&mut vertices, &CANVAS, from, to, 3.0, [1.0; 4]);
+        let pixels: Vec<[f32; 2]> = vertices.iter().map(|v| CANVAS.to_pixels(v.position)).collect();
+        let area: f32 = pixels.chunks(3).map(|t| cross(t[0], t[1], t[2]).abs() * 0.5).sum();
+        let (a, b) = (CANVAS.to_pixels(from), CANVAS.to_pixels(to));
+        let length = ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt();
+        assert!((area / length - 3.0).abs() < 1e-2);
+    }
+}
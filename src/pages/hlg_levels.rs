@@ -0,0 +1,58 @@
+use crate::transfer::hlg;
+use crate::ui::HdrTextLabel;
+use super::layout::{CellShape, GridLayout};
+use super::{Page, PageOutput, RenderContext, add_quad, format_nits, nits_to_scrgb};
+
+/// Grid of HLG signal steps rendered for the current display peak
+pub struct HlgLevels {
+    /// Signal levels as fractions (0..1)
+    pub signals: Vec<f32>,
+}
+
+impl Default for HlgLevels {
+    fn default() -> Self {
+        Self {
+            signals: vec![
+                0.0, 0.02, 0.05, 0.10,
+                0.20, 0.30, 0.40, 0.50,
+                0.60, 0.70, 0.75, 0.80,
+                0.85, 0.90, 0.95, 1.0,
+            ],
+        }
+    }
+}
+
+impl Page for HlgLevels {
+    fn name(&self) -> &'static str {
+        "HLG Levels"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+
+        let layout = GridLayout { shape: CellShape::Square, ..GridLayout::default() };
+        let cells = layout.cells(self.signals.len(), ctx.width, ctx.height);
+
+        for (cell, &signal) in cells.iter().zip(&self.signals) {
+            let nits = hlg::signal_to_nits(signal, ctx.max_brightness_nits);
+            let scrgb_value = nits_to_scrgb(nits);
+
+            let color = [scrgb_value, scrgb_value, scrgb_value, 1.0];
+            add_quad(&mut vertices, cell.rect.x0, cell.rect.y0, cell.rect.x1, cell.rect.y1, color);
+
+            labels.push(HdrTextLabel {
+                text: format!("HLG:{:.0}% {}nits", signal * 100.0, format_nits(nits)),
+                x: cell.label.x0,
+                y: cell.label.y0,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        PageOutput { vertices, labels }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::color::{BT709, BT2020, Color};
+use crate::transfer::pq;
+use crate::ui::HdrTextLabel;
+use std::f32::consts::TAU;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::params::{Param, ParamId, Params};
+use super::geometry::{Canvas, add_ring_sector};
+use super::{Page, PageAction, PageOutput, RenderContext, format_nits, nits_to_scrgb};
+
+/// Hue wheel at constant ICtCp intensity, with one ring per saturation step.
+/// Colors the display cannot be sent without clipping are flagged instead.
+/// Intensity is given as the luminance of the gray with the same I, and
+/// chroma as the CtCp distance of the 100% ring.
+pub struct HueWheel {
+    params: Params,
+}
+
+const GRAY: ParamId<f32> = ParamId::new("gray");
+const CHROMA: ParamId<f32> = ParamId::new("chroma");
+
+const SATURATIONS: [f32; 4] = [0.25, 0.5, 0.75, 1.0];
+const HUE_SEGMENTS: usize = 72;
+/// Ring width taken by the out-of-gamut flag
+const FLAG_FRACTION: f32 = 0.3;
+const OUTSIDE_BT709_NITS: f32 = 30.0;
+const OUTSIDE_BT2020_NITS: f32 = 120.0;
+
+impl Default for HueWheel {
+    fn default() -> Self {
+        Self {
+            params: Params::new(vec![
+                Param::nits(GRAY, "Gray", 100.0, 1.0, 5000.0),
+                Param::float(CHROMA, "100% chroma", 0.25, 0.05, 0.6),
+            ]),
+        }
+    }
+}
+
+impl Page for HueWheel {
+    fn name(&self) -> &'static str {
+        "Hue Wheel (ICtCp)"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+        let canvas = Canvas::new(ctx.width, ctx.height);
+
+        let gray_nits = self.params.float(GRAY);
+        let full_chroma = self.params.float(CHROMA);
+        let intensity = pq::inverse_eotf(gray_nits);
+        let center = [0.0, 0.05];
+        let radius = 0.8;
+        let ring_width = radius / SATURATIONS.len() as f32;
+        let gap = 0.01;
+
+        let outside_709 = nits_to_scrgb(OUTSIDE_BT709_NITS);
+        let outside_709 = [outside_709, outside_709, outside_709, 1.0];
+        let outside_2020 = nits_to_scrgb(OUTSIDE_BT2020_NITS);
+        let outside_2020 = [outside_2020, outside_2020, outside_2020, 1.0];
+
+        for (ring, &saturation) in SATURATIONS.iter().enumerate() {
+            let chroma = saturation * full_chroma;
+            let inner = ring as f32 * ring_width + gap;
+            let outer = (ring + 1) as f32 * ring_width;
+            let flag_inner = outer - (outer - inner) * FLAG_FRACTION;
+
+            let color_at = |hue: f32| Color::from_ictcp([intensity, chroma * hue.cos(), chroma * hue.sin()]);
+
+            for segment in 0..HUE_SEGMENTS {
+                let a0 = segment as f32 / HUE_SEGMENTS as f32 * TAU;
+                let a1 = (segment + 1) as f32 / HUE_SEGMENTS as f32 * TAU;
+                let (c0, c1) = (color_at(a0), color_at(a1));
+                let middle = color_at((a0 + a1) * 0.5);
+
+                if !middle.is_inside(&BT2020) {
+                    add_ring_sector(&mut vertices, &canvas, center, (flag_inner, outer), (a0, a1), (outside_2020, outside_2020));
+                    continue;
+                }
+                let colors = (c0.to_scrgb(), c1.to_scrgb());
+                if middle.is_inside(&BT709) {
+                    add_ring_sector(&mut vertices, &canvas, center, (inner, outer), (a0, a1), colors);
+                } else {
+                    add_ring_sector(&mut vertices, &canvas, center, (inner, flag_inner), (a0, a1), colors);
+                    add_ring_sector(&mut vertices, &canvas, center, (flag_inner, outer), (a0, a1), (outside_709, outside_709));
+                }
+            }
+
+            let label_pos = canvas.polar(center, (inner + outer) * 0.5, TAU * 0.25);
+            labels.push(HdrTextLabel {
+                text: format!("{:.0}%", saturation * 100.0),
+                x: label_pos[0] - 0.02,
+                y: label_pos[1] + 0.02,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        labels.push(HdrTextLabel {
+            text: format!(
+                "I = {:.3} (gray {}nits)  100% = CtCp chroma {:.2}  Up/Down: intensity  Left/Right: chroma",
+                intensity,
+                format_nits(gray_nits),
+                full_chroma
+            ),
+            x: -0.98,
+            y: -0.86,
+            nits: 40.0,
+            size: font_size,
+        });
+        labels.push(HdrTextLabel {
+            text: format!(
+                "Outer edge at {}nits: outside BT.709.  Edge only at {}nits: outside BT.2020, not drawn",
+                format_nits(OUTSIDE_BT709_NITS),
+                format_nits(OUTSIDE_BT2020_NITS)
+            ),
+            x: -0.98,
+            y: -0.92,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        let gray_nits = self.params.float(GRAY);
+        let full_chroma = self.params.float(CHROMA);
+        match key {
+            Key::Named(NamedKey::ArrowUp) => self.params.set(GRAY, (gray_nits * 2.0).min(5000.0)),
+            Key::Named(NamedKey::ArrowDown) => self.params.set(GRAY, (gray_nits / 2.0).max(1.0)),
+            Key::Named(NamedKey::ArrowRight) => self.params.set(CHROMA, (full_chroma + 0.05).min(0.6)),
+            Key::Named(NamedKey::ArrowLeft) => self.params.set(CHROMA, (full_chroma - 0.05).max(0.05)),
+            _ => {}
+        }
+        None
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
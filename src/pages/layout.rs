@@ -0,0 +1,285 @@
+//! Grid layout for pages of labeled patches. Sizes are worked out in viewport
+//! pixels, so cells keep their shape, and returned in page NDC.
+
+use crate::dx12::letterbox_viewport;
+
+/// Rectangle in page NDC, `y0` at the top
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub x0: f32,
+    pub y0: f32,
+    pub x1: f32,
+    pub y1: f32,
+}
+
+impl Rect {
+    pub const FULL: Rect = Rect { x0: -1.0, y0: 1.0, x1: 1.0, y1: -1.0 };
+
+    pub fn width(&self) -> f32 {
+        self.x1 - self.x0
+    }
+
+    pub fn height(&self) -> f32 {
+        self.y0 - self.y1
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Spacing {
+    Pixels(f32),
+    /// Percent of the viewport's shorter side
+    Percent(f32),
+}
+
+impl Spacing {
+    fn pixels(self, viewport: [f32; 2]) -> f32 {
+        match self {
+            Spacing::Pixels(pixels) => pixels,
+            Spacing::Percent(percent) => percent / 100.0 * viewport[0].min(viewport[1]),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CellShape {
+    /// Stretch cells to fill the area
+    Fill,
+    /// Keep width / height fixed, centering the grid in the area
+    Aspect(f32),
+    Square,
+}
+
+impl CellShape {
+    fn aspect(self) -> Option<f32> {
+        match self {
+            CellShape::Fill => None,
+            CellShape::Aspect(aspect) => Some(aspect),
+            CellShape::Square => Some(1.0),
+        }
+    }
+}
+
+pub struct Cell {
+    pub rect: Rect,
+    /// Space under the cell for its text; labels go at the top-left corner
+    pub label: Rect,
+}
+
+/// Cells fill rows left to right, top to bottom. Leaving `cols` or `rows`
+/// as `None` picks them to make the cells as large as possible.
+#[derive(Clone, Copy, Debug)]
+pub struct GridLayout {
+    /// Area of the page the grid goes in, before the margin
+    pub bounds: Rect,
+    pub cols: Option<usize>,
+    pub rows: Option<usize>,
+    pub shape: CellShape,
+    pub margin: Spacing,
+    /// Between neighbouring cells, and between a label and the next row
+    pub gap: Spacing,
+    pub label_height: Spacing,
+}
+
+/// Space between a cell and the top of its label
+const LABEL_GAP: f32 = 4.0;
+
+impl Default for GridLayout {
+    fn default() -> Self {
+        Self {
+            bounds: Rect::FULL,
+            cols: None,
+            rows: None,
+            shape: CellShape::Fill,
+            margin: Spacing::Percent(4.0),
+            gap: Spacing::Percent(2.5),
+            label_height: Spacing::Percent(2.5),
+        }
+    }
+}
+
+impl GridLayout {
+    /// Lay out `count` cells on a page rendered into a `width` x `height` window
+    pub fn cells(&self, count: usize, width: u32, height: u32) -> Vec<Cell> {
+        let (vp_width, vp_height, _, _) = letterbox_viewport(width, height);
+        self.cells_in(count, [vp_width, vp_height])
+    }
+
+    /// Lay out `count` cells in a viewport of the given size in pixels
+    pub fn cells_in(&self, count: usize, viewport: [f32; 2]) -> Vec<Cell> {
+        if count == 0 {
+            return Vec::new();
+        }
+        let margin = self.margin.pixels(viewport);
+        let gap = self.gap.pixels(viewport);
+        let label = self.label_height.pixels(viewport);
+
+        // Bounds in pixels from the viewport's top-left corner
+        let left = (self.bounds.x0 + 1.0) / 2.0 * viewport[0] + margin;
+        let top = (1.0 - self.bounds.y0) / 2.0 * viewport[1] + margin;
+        let area = [
+            (self.bounds.width() / 2.0 * viewport[0] - 2.0 * margin).max(0.0),
+            (self.bounds.height() / 2.0 * viewport[1] - 2.0 * margin).max(0.0),
+        ];
+
+        let (cols, rows) = self.grid(count, area, gap, label);
+        let (mut cell_w, mut cell_h) = cell_size(cols, rows, area, gap, label);
+        if let Some(aspect) = self.shape.aspect() {
+            if cell_w > cell_h * aspect {
+                cell_w = cell_h * aspect;
+            } else {
+                cell_h = cell_w / aspect;
+            }
+        }
+        let grid_w = cols as f32 * cell_w + (cols - 1) as f32 * gap;
+        let grid_h = rows as f32 * (cell_h + label) + (rows - 1) as f32 * gap;
+        let left = left + (area[0] - grid_w).max(0.0) / 2.0;
+        let top = top + (area[1] - grid_h).max(0.0) / 2.0;
+
+        let to_ndc = |x: f32, y: f32| (x / viewport[0] * 2.0 - 1.0, 1.0 - y / viewport[1] * 2.0);
+        let rect = |x0: f32, y0: f32, x1: f32, y1: f32| {
+            let (x0, y0) = to_ndc(x0, y0);
+            let (x1, y1) = to_ndc(x1, y1);
+            Rect { x0, y0, x1, y1 }
+        };
+
+        (0..count)
+            .map(|index| {
+                let x = left + (index % cols) as f32 * (cell_w + gap);
+                let y = top + (index / cols) as f32 * (cell_h + label + gap);
+                let label_top = y + cell_h + LABEL_GAP.min(label);
+                Cell {
+                    rect: rect(x, y, x + cell_w, y + cell_h),
+                    label: rect(x, label_top, x + cell_w, y + cell_h + label),
+                }
+            })
+            .collect()
+    }
+
+    /// Columns and rows for `count` cells
+    fn grid(&self, count: usize, area: [f32; 2], gap: f32, label: f32) -> (usize, usize) {
+        match (self.cols, self.rows) {
+            (Some(cols), _) => (cols.max(1), count.div_ceil(cols.max(1))),
+            (None, Some(rows)) => (count.div_ceil(rows.max(1)), rows.max(1).min(count)),
+            (None, None) => {
+                // Biggest cells of the target shape, then the fewest empty slots
+                let aspect = self.shape.aspect().unwrap_or(1.0);
+                let mut best = (1, count);
+                let mut best_size = -1.0f32;
+                for cols in 1..=count {
+                    let rows = count.div_ceil(cols);
+                    let (w, h) = cell_size(cols, rows, area, gap, label);
+                    let size = w.min(h * aspect);
+                    if size > best_size * 1.0001 {
+                        best = (cols, rows);
+                        best_size = size;
+                    }
+                }
+                best
+            }
+        }
+    }
+}
+
+/// Largest cell that fits `cols` x `rows` in the area
+fn cell_size(cols: usize, rows: usize, area: [f32; 2], gap: f32, label: f32) -> (f32, f32) {
+    let w = (area[0] - (cols - 1) as f32 * gap) / cols as f32;
+    let h = (area[1] - (rows - 1) as f32 * gap) / rows as f32 - label;
+    (w.max(0.0), h.max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel_size(rect: &Rect, viewport: [f32; 2]) -> (f32, f32) {
+        (rect.width() / 2.0 * viewport[0], rect.height() / 2.0 * viewport[1])
+    }
+
+    fn assert_inside(cells: &[Cell], bounds: Rect) {
+        for cell in cells {
+            for rect in [cell.rect, cell.label] {
+                assert!(rect.x0 >= bounds.x0 - 1e-4 && rect.x1 <= bounds.x1 + 1e-4, "{rect:?}");
+                assert!(rect.y0 <= bounds.y0 + 1e-4 && rect.y1 >= bounds.y1 - 1e-4, "{rect:?}");
+                assert!(rect.x0 <= rect.x1 && rect.y1 <= rect.y0, "{rect:?}");
+            }
+        }
+    }
+
+    fn assert_no_overlap(cells: &[Cell]) {
+        for (i, a) in cells.iter().enumerate() {
+            for b in &cells[i + 1..] {
+                let apart = a.rect.x1 <= b.rect.x0 + 1e-4
+                    || b.rect.x1 <= a.rect.x0 + 1e-4
+                    || a.label.y1 >= b.rect.y0 - 1e-4
+                    || b.label.y1 >= a.rect.y0 - 1e-4;
+                assert!(apart, "{:?} overlaps {:?}", a.rect, b.rect);
+            }
+        }
+    }
+
+    #[test]
+    fn odd_counts_fill_rows_in_order() {
+        let viewport = [1920.0, 1080.0];
+        for count in [1, 3, 5, 7, 13, 17, 23] {
+            let cells = GridLayout { shape: CellShape::Square, ..GridLayout::default() }.cells_in(count, viewport);
+            assert_eq!(cells.len(), count);
+            assert_inside(&cells, Rect::FULL);
+            assert_no_overlap(&cells);
+            for cell in &cells {
+                let (w, h) = pixel_size(&cell.rect, viewport);
+                assert!((w - h).abs() < 0.01, "{count} cells: {w} x {h}");
+            }
+        }
+
+        let cells = GridLayout { cols: Some(3), ..GridLayout::default() }.cells_in(7, viewport);
+        assert_eq!(cells[3].rect.x0, cells[0].rect.x0);
+        assert!(cells[3].rect.y0 < cells[0].label.y1);
+        assert_eq!(cells[6].rect.x0, cells[0].rect.x0);
+    }
+
+    #[test]
+    fn extreme_aspects_use_a_single_row_or_column() {
+        let layout = GridLayout { shape: CellShape::Square, ..GridLayout::default() };
+
+        let tall = layout.cells_in(5, [100.0, 4000.0]);
+        assert!(tall.iter().all(|cell| cell.rect.x0 == tall[0].rect.x0));
+        assert_inside(&tall, Rect::FULL);
+        assert_no_overlap(&tall);
+
+        let wide = layout.cells_in(5, [8000.0, 200.0]);
+        assert!(wide.iter().all(|cell| cell.rect.y0 == wide[0].rect.y0));
+        assert_inside(&wide, Rect::FULL);
+        assert_no_overlap(&wide);
+
+        // Margins larger than the viewport leave empty cells, not inverted ones
+        let squashed = GridLayout { margin: Spacing::Pixels(100.0), ..GridLayout::default() }.cells_in(4, [50.0, 50.0]);
+        assert!(squashed.iter().all(|cell| cell.rect.width() == 0.0 && cell.rect.height() == 0.0));
+    }
+
+    #[test]
+    fn spacing_in_pixels_and_percent() {
+        let viewport = [1920.0, 1080.0];
+        let layout = GridLayout {
+            cols: Some(2),
+            margin: Spacing::Pixels(0.0),
+            gap: Spacing::Pixels(20.0),
+            label_height: Spacing::Percent(10.0),
+            ..GridLayout::default()
+        };
+        let cells = layout.cells_in(4, viewport);
+        let gap = (cells[1].rect.x0 - cells[0].rect.x1) / 2.0 * viewport[0];
+        let label = (cells[0].rect.y1 - cells[0].label.y1) / 2.0 * viewport[1];
+        assert!((gap - 20.0).abs() < 0.01);
+        assert!((label - 108.0).abs() < 0.01);
+        assert_eq!(cells[0].rect.x0, -1.0);
+        assert_eq!(cells[0].rect.y0, 1.0);
+    }
+
+    #[test]
+    fn bounds_limit_the_grid() {
+        let bounds = Rect { x0: -0.5, y0: 0.3, x1: 1.0, y1: -0.85 };
+        let cells = GridLayout { bounds, ..GridLayout::default() }.cells_in(12, [1920.0, 1080.0]);
+        assert_inside(&cells, bounds);
+        assert_no_overlap(&cells);
+    }
+}
@@ -1,27 +1,115 @@
 mod animated_gradient;
+mod banding;
+mod blooming;
 mod brightness_grid;
+mod checkerboard;
+mod chromaticity;
 mod color_ramps;
+mod colorchecker;
+mod frame_cadence;
+mod gamut_rings;
+mod geometry;
+mod hlg_levels;
+mod hue_wheel;
+mod layout;
+mod motion_resolution;
+mod near_black;
+pub mod params;
+mod peak_calibration;
 mod pq_levels;
+mod reference_levels;
 mod split_compare;
+mod sustained_brightness;
+mod tone_curves;
+mod uniformity;
+mod window_pattern;
+mod zone_grid;
 
+pub use crate::dx12::VIEWPORT_ASPECT;
+use crate::clock::PageClock;
 use crate::dx12::Vertex;
+use crate::transfer::BitDepth;
 use crate::ui::HdrTextLabel;
+use params::Params;
+use winit::keyboard::{Key, ModifiersState};
+
+/// Everything a page may draw from besides its own state
+pub struct RenderContext<'a> {
+    /// Window size in pixels
+    pub width: u32,
+    pub height: u32,
+    pub max_brightness_nits: f32,
+    pub paper_white_nits: f32,
+    pub clock: &'a PageClock,
+    /// Frames presented so far, advancing every frame even while the clock
+    /// is paused or stepping
+    pub presents: u64,
+}
 
 pub struct PageOutput {
     pub vertices: Vec<Vertex>,
     pub labels: Vec<HdrTextLabel>,
 }
 
+/// Changes to app-wide settings requested by a page
+pub enum PageAction {
+    SetMaxBrightness(f32),
+}
+
 pub trait Page {
     fn name(&self) -> &'static str;
-    fn render(&self, width: u32, height: u32, max_brightness_nits: f32, time: f32) -> PageOutput;
+    fn render(&self, ctx: &RenderContext) -> PageOutput;
+
+    /// Handle a key press not used by the app while this page is shown
+    fn on_key(&mut self, _key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        None
+    }
+
+    /// Pointer position in page NDC, and whether the left button is held
+    fn on_pointer(&mut self, _pos: [f32; 2], _pressed: bool) {}
+
+    /// Page-specific controls, shown in the egui panel under the page name
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    /// Typed parameters, given controls automatically and saved per page
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        None
+    }
+
+    /// Called with the window size in pixels before the first frame and
+    /// whenever it changes, whether or not the page is shown
+    fn resize(&mut self, _width: u32, _height: u32) {}
+
+    /// Called once per frame while the page is shown, before rendering
+    fn update(&mut self) {}
+
+    /// Called when another page replaces this one, and for the current page
+    /// when the app exits
+    fn on_hide(&mut self) {}
 }
 
 pub fn nits_to_scrgb(nits: f32) -> f32 {
     nits / 80.0
 }
 
-pub fn add_quad(vertices: &mut Vec<Vertex>, x0: f32, y0: f32, x1: f32, y1: f32, color: [f32; 4]) {
+/// Format a luminance for labels with precision that suits its magnitude
+pub fn format_nits(nits: f32) -> String {
+    if nits == nits.floor() || nits >= 100.0 {
+        format!("{:.0}", nits)
+    } else if nits >= 10.0 {
+        format!("{:.1}", nits)
+    } else if nits >= 1.0 {
+        format!("{:.2}", nits)
+    } else {
+        // Two significant digits for shadow levels
+        let decimals = (1.0 - nits.log10().floor()).clamp(2.0, 6.0) as usize;
+        format!("{:.*}", decimals, nits)
+    }
+}
+
+/// Axis-aligned quad; `color` is an scRGB value or a `Color` in any space
+pub fn add_quad(vertices: &mut Vec<Vertex>, x0: f32, y0: f32, x1: f32, y1: f32, color: impl Into<[f32; 4]>) {
+    let color = color.into();
     let uv = [1.0, 1.0];
     vertices.push(Vertex {
         position: [x0, y0],
@@ -55,54 +143,181 @@ pub fn add_quad(vertices: &mut Vec<Vertex>, x0: f32, y0: f32, x1: f32, y1: f32,
     });
 }
 
-pub fn add_gradient_quad_h(
-    vertices: &mut Vec<Vertex>,
-    x0: f32,
-    y0: f32,
-    x1: f32,
-    y1: f32,
-    left_color: [f32; 4],
-    right_color: [f32; 4],
-) {
+/// Triangle with a color per vertex
+pub fn add_triangle(vertices: &mut Vec<Vertex>, positions: [[f32; 2]; 3], colors: [[f32; 4]; 3]) {
     let uv = [1.0, 1.0];
-    vertices.push(Vertex {
-        position: [x0, y0],
-        uv,
-        color: left_color,
-    });
-    vertices.push(Vertex {
-        position: [x0, y1],
-        uv,
-        color: left_color,
-    });
-    vertices.push(Vertex {
-        position: [x1, y1],
-        uv,
-        color: right_color,
-    });
-    vertices.push(Vertex {
-        position: [x0, y0],
-        uv,
-        color: left_color,
-    });
-    vertices.push(Vertex {
-        position: [x1, y1],
-        uv,
-        color: right_color,
-    });
-    vertices.push(Vertex {
-        position: [x1, y0],
-        uv,
-        color: right_color,
-    });
+    for (position, color) in positions.into_iter().zip(colors) {
+        vertices.push(Vertex { position, uv, color });
+    }
+}
+
+/// Signal encoding used when quantizing, see `Quantization`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Linear,
+    Gamma22,
+    Pq,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 3] = [Encoding::Linear, Encoding::Gamma22, Encoding::Pq];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Linear => "Linear",
+            Encoding::Gamma22 => "Gamma 2.2",
+            Encoding::Pq => "PQ",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Dither {
+    None,
+    Ordered,
+    BlueNoise,
+}
+
+impl Dither {
+    pub const ALL: [Dither; 3] = [Dither::None, Dither::Ordered, Dither::BlueNoise];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Dither::None => "Plain",
+            Dither::Ordered => "Ordered",
+            Dither::BlueNoise => "Blue noise",
+        }
+    }
+}
+
+/// Per-pixel quantization done by the quad shader, simulating an integer
+/// pipeline between the interpolated color and the swapchain
+#[derive(Clone, Copy)]
+pub struct Quantization {
+    pub bit_depth: BitDepth,
+    pub encoding: Encoding,
+    pub dither: Dither,
+    /// Luminance of signal 1.0 for the linear and gamma encodings
+    pub peak_nits: f32,
 }
 
-pub fn get_pages() -> Vec<Box<dyn Page>> {
+impl Quantization {
+    /// Packed into the vertex uv, which solid quads otherwise leave at (1, 1)
+    fn uv(&self) -> [f32; 2] {
+        let mode = self.bit_depth.bits() | (self.encoding as u32) << 4 | (self.dither as u32) << 6;
+        [mode as f32, self.peak_nits]
+    }
+}
+
+/// Quantize the given vertices, usually the ones a page just added
+pub fn quantize_vertices(vertices: &mut [Vertex], quantization: Quantization) {
+    let uv = quantization.uv();
+    for vertex in vertices {
+        vertex.uv = uv;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Category {
+    Luminance,
+    Color,
+    Motion,
+    /// Local dimming, blooming and panel uniformity
+    Uniformity,
+    ToneMapping,
+}
+
+impl Category {
+    pub const ALL: [Category; 5] = [
+        Category::Luminance,
+        Category::Color,
+        Category::Motion,
+        Category::Uniformity,
+        Category::ToneMapping,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::Luminance => "Luminance",
+            Category::Color => "Color",
+            Category::Motion => "Motion",
+            Category::Uniformity => "Uniformity & Dimming",
+            Category::ToneMapping => "Tone Mapping",
+        }
+    }
+}
+
+/// A page as listed in the registry
+pub struct PageEntry {
+    /// Stable name used on the command line and in saved settings
+    pub id: &'static str,
+    pub category: Category,
+    pub description: &'static str,
+    /// Disabled pages are left out of the picker and page cycling, but can
+    /// still be opened by ID
+    pub enabled: bool,
+    pub page: Box<dyn Page>,
+}
+
+fn entry(id: &'static str, category: Category, description: &'static str, page: impl Page + 'static) -> PageEntry {
+    PageEntry { id, category, description, enabled: true, page: Box::new(page) }
+}
+
+pub fn get_pages() -> Vec<PageEntry> {
+    use Category::*;
     vec![
-        Box::new(pq_levels::PqLevels),
-        //Box::new(brightness_grid::BrightnessGrid),
-        Box::new(color_ramps::ColorRamps),
-        Box::new(animated_gradient::AnimatedGradient),
-        Box::new(split_compare::SplitCompare),
+        entry("pq-levels", Luminance, "Grid of patches at PQ code values, labeled with their luminance", pq_levels::PqLevels::default()),
+        entry("hlg-levels", Luminance, "HLG signal steps rendered for the current display peak", hlg_levels::HlgLevels::default()),
+        entry("near-black", Luminance, "Fine PQ steps above black with PLUGE bars", near_black::NearBlack::default()),
+        entry("reference-levels", Luminance, "BT.2408 reference levels next to the paper white setting", reference_levels::ReferenceLevels),
+        PageEntry {
+            enabled: false,
+            ..entry("brightness-grid", Luminance, "Grid of editable nit levels", brightness_grid::BrightnessGrid::default())
+        },
+        entry("banding", Luminance, "Smooth ramps quantized to a chosen bit depth, with optional dither", banding::Banding::default()),
+        entry("split-compare", Luminance, "Gray steps clamped to SDR white next to the same steps in HDR", split_compare::SplitCompare::default()),
+        entry("peak-calibration", Luminance, "HGIG-style clipping point search for the display peak", peak_calibration::PeakCalibration::default()),
+        entry("window", Luminance, "Centered window for measuring ABL and peak against window size", window_pattern::WindowPattern::default()),
+        entry("sustained-brightness", Luminance, "Timed window for watching ABL dim the panel over time", sustained_brightness::SustainedBrightness::default()),
+        entry("color-ramps", Color, "Ramps from black through each base color to white", color_ramps::ColorRamps::default()),
+        entry("gamut-rings", Color, "Nested primaries and secondaries in BT.2020, P3 and BT.709", gamut_rings::GamutRings),
+        entry("chromaticity-xy", Color, "CIE 1931 xy diagram with the standard gamuts", chromaticity::Chromaticity { diagram: chromaticity::Diagram::Xy }),
+        entry("chromaticity-uv", Color, "CIE 1976 u'v' diagram with the standard gamuts", chromaticity::Chromaticity { diagram: chromaticity::Diagram::UvPrime }),
+        entry("hue-wheel", Color, "Constant-intensity ICtCp hues, flagging those outside each gamut", hue_wheel::HueWheel::default()),
+        entry("colorchecker", Color, "The 24 ColorChecker patches at a chosen reference white", colorchecker::ColorChecker::default()),
+        entry("animated-gradient", Motion, "Gradient towards a slowly cycling color", animated_gradient::AnimatedGradient),
+        entry("motion-resolution", Motion, "Frame-locked pursuit pattern for motion blur", motion_resolution::MotionResolution::default()),
+        entry("frame-cadence", Motion, "One cell lit per frame to reveal skipped or repeated frames", frame_cadence::FrameCadence::default()),
+        entry("blooming", Uniformity, "Small bright objects on black for judging local dimming halos", blooming::Blooming::default()),
+        entry("zone-grid", Uniformity, "Lights one cell or row at a time to map dimming zones", zone_grid::ZoneGrid::default()),
+        entry("uniformity", Uniformity, "Full field with measurement targets for a uniformity map", uniformity::Uniformity::default()),
+        entry("ansi-checkerboard", Uniformity, "4x4 checkerboard for simultaneous contrast", checkerboard::AnsiCheckerboard::default()),
+        entry("tone-curves", ToneMapping, "Selected operator's curve for 4000 and 10000 nit masters, with ramps", tone_curves::ToneCurves::default()),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reading a parameter a page never declared panics, so render each
+    /// page once with its defaults
+    #[test]
+    fn pages_render_with_default_params() {
+        let clock = PageClock::new();
+        let ctx = RenderContext {
+            width: 1920,
+            height: 1080,
+            max_brightness_nits: 1000.0,
+            paper_white_nits: 203.0,
+            clock: &clock,
+            presents: 0,
+        };
+        for mut entry in get_pages() {
+            entry.page.resize(ctx.width, ctx.height);
+            entry.page.update();
+            let output = entry.page.render(&ctx);
+            assert!(!output.vertices.is_empty() || !output.labels.is_empty(), "{}", entry.id);
+        }
+    }
+}
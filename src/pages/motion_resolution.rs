@@ -0,0 +1,137 @@
+use crate::dx12::{letterbox_viewport, Vertex};
+use crate::ui::HdrTextLabel;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageAction, PageOutput, RenderContext, add_quad, format_nits, nits_to_scrgb};
+
+/// Line-pair bars and text scrolling at a whole number of pixels per frame,
+/// for judging motion resolution with a pursuit camera. Motion follows the
+/// frame index, so every frame moves by the same amount regardless of timing.
+pub struct MotionResolution {
+    params: Params,
+}
+
+const SPEED: ParamId<i32> = ParamId::new("speed");
+const NITS: ParamId<f32> = ParamId::new("nits");
+
+/// Line width in pixels and line count of each group in a bar tile
+const LINE_GROUPS: [(u32, u32); 5] = [(1, 8), (2, 6), (3, 4), (4, 4), (8, 2)];
+const GROUP_GAP_PX: u32 = 16;
+const TILE_PX: u32 = 256;
+const TEXT_TILE_PX: u32 = 512;
+
+impl Default for MotionResolution {
+    fn default() -> Self {
+        Self {
+            params: Params::new(vec![
+                Param::int(SPEED, "Speed (px/frame)", 8, 1, 64),
+                Param::nits(NITS, "Lines", 200.0, 10.0, 1000.0),
+            ]),
+        }
+    }
+}
+
+impl MotionResolution {
+    /// One row of bar tiles between `y0` and `y1`, shifted right by `offset_px`
+    fn add_bars(&self, vertices: &mut Vec<Vertex>, vp_width: f32, offset_px: u32, y0: f32, y1: f32) {
+        let px = 2.0 / vp_width;
+        let value = nits_to_scrgb(self.params.float(NITS));
+        let color = [value, value, value, 1.0];
+
+        let tiles = (vp_width as u32).div_ceil(TILE_PX) + 1;
+        for tile in 0..tiles {
+            let mut x = (tile * TILE_PX + offset_px % TILE_PX) as f32 - TILE_PX as f32 + GROUP_GAP_PX as f32;
+            for (line_px, count) in LINE_GROUPS {
+                for _ in 0..count {
+                    let x0 = -1.0 + x * px;
+                    add_quad(vertices, x0, y0, x0 + line_px as f32 * px, y1, color);
+                    x += 2.0 * line_px as f32;
+                }
+                x += GROUP_GAP_PX as f32;
+            }
+        }
+    }
+}
+
+impl Page for MotionResolution {
+    fn name(&self) -> &'static str {
+        "Motion Resolution (Pursuit)"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+
+        let (vp_width, _, _, _) = letterbox_viewport(ctx.width, ctx.height);
+        let px = 2.0 / vp_width;
+        // Both tiles repeat every TEXT_TILE_PX pixels, so wrap the frame
+        // index first and keep long runs exact
+        let frame = ctx.clock.frame();
+        let period = TEXT_TILE_PX as u64;
+        let pixels_per_frame = self.params.int(SPEED) as u64;
+        let offset_px = ((frame % period) * pixels_per_frame % period) as u32;
+
+        labels.push(HdrTextLabel {
+            text: "Static reference".to_string(),
+            x: -0.98,
+            y: 0.9,
+            nits: 40.0,
+            size: font_size,
+        });
+        self.add_bars(&mut vertices, vp_width, 0, 0.82, 0.42);
+
+        labels.push(HdrTextLabel {
+            text: format!("Moving {} px/frame", pixels_per_frame),
+            x: -0.98,
+            y: 0.32,
+            nits: 40.0,
+            size: font_size,
+        });
+        self.add_bars(&mut vertices, vp_width, offset_px, 0.24, -0.16);
+
+        let text_offset = offset_px % TEXT_TILE_PX;
+        let text_tiles = (vp_width as u32).div_ceil(TEXT_TILE_PX) + 1;
+        for tile in 0..text_tiles {
+            let x = (tile * TEXT_TILE_PX + text_offset) as f32 - TEXT_TILE_PX as f32;
+            labels.push(HdrTextLabel {
+                text: "Pursuit 0123456789".to_string(),
+                x: -1.0 + x * px,
+                y: -0.3,
+                nits: 40.0,
+                size: font_size * 3.0,
+            });
+        }
+
+        labels.push(HdrTextLabel {
+            text: format!(
+                "Frame {}  lines {}nits  Up/Down: speed (Shift: x8)",
+                frame,
+                format_nits(self.params.float(NITS))
+            ),
+            x: -0.98,
+            y: -0.9,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, modifiers: ModifiersState) -> Option<PageAction> {
+        let step = if modifiers.shift_key() { 8 } else { 1 };
+        let speed = self.params.int(SPEED);
+        match key {
+            Key::Named(NamedKey::ArrowUp) => self.params.set(SPEED, (speed + step).min(64)),
+            Key::Named(NamedKey::ArrowDown) => self.params.set(SPEED, (speed - step).max(1)),
+            _ => {}
+        }
+        None
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
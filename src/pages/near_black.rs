@@ -0,0 +1,194 @@
+use crate::transfer::{pq, BitDepth, CodeFormat, Range};
+use crate::ui::HdrTextLabel;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::layout::{CellShape, GridLayout, Rect, Spacing};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageAction, PageOutput, RenderContext, add_quad, format_nits, nits_to_scrgb};
+
+/// Shadow detail: fine PQ code steps above black, with PLUGE bars
+pub struct NearBlack {
+    params: Params,
+}
+
+/// First code of the step grid
+const START: ParamId<i32> = ParamId::new("start");
+/// Code increment between patches
+const STEP: ParamId<i32> = ParamId::new("step");
+/// Number of patches in the step grid
+const COUNT: ParamId<i32> = ParamId::new("count");
+/// 10-bit range of both the step grid and the PLUGE bars
+const NARROW: ParamId<bool> = ParamId::new("narrow");
+
+/// Step sizes the Left/Right keys move between
+const STEP_SIZES: [i32; 8] = [1, 2, 4, 5, 8, 10, 15, 20];
+const MAX_COUNT: i32 = 32;
+
+impl Default for NearBlack {
+    fn default() -> Self {
+        Self {
+            params: Params::new(vec![
+                Param::int(START, "First code", 0, 0, 1023),
+                Param::int(STEP, "Code step", 10, 1, 20),
+                Param::int(COUNT, "Patches", 16, 1, MAX_COUNT),
+                Param::bool(NARROW, "Narrow range", false),
+            ]),
+        }
+    }
+}
+
+/// PLUGE bars per ITU-R BT.814, as offsets from black in percent of the
+/// signal range, with a 0% black bar between them. scRGB cannot carry a
+/// signal below black, so the below-black bars render at black; they must
+/// stay invisible, while the above-black bars must be just visible.
+const PLUGE_BARS: [f32; 5] = [-4.0, -2.0, 0.0, 2.0, 4.0];
+
+impl NearBlack {
+    fn format(&self) -> CodeFormat {
+        let range = if self.params.bool(NARROW) {
+            Range::Narrow
+        } else {
+            Range::Full
+        };
+        CodeFormat::new(BitDepth::Ten, range)
+    }
+
+    /// Code of the patch at `index`, stopping at white
+    fn code(&self, index: usize) -> u16 {
+        let code = self.params.int(START) + self.params.int(STEP) * index as i32;
+        (code as u16).min(self.format().white_code())
+    }
+
+    fn count(&self) -> usize {
+        self.params.int(COUNT) as usize
+    }
+}
+
+impl Page for NearBlack {
+    fn name(&self) -> &'static str {
+        "Near Black / PLUGE"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+        let format = self.format();
+
+        // PLUGE bars across the top third, on black
+        let bar_top = 0.9f32;
+        let bar_bottom = 0.45f32;
+        let bar_width = 0.12f32;
+        let bar_gap = 0.25f32;
+        let bars_x0 = -(PLUGE_BARS.len() as f32 * bar_width + (PLUGE_BARS.len() - 1) as f32 * bar_gap) / 2.0;
+
+        for (i, percent) in PLUGE_BARS.iter().enumerate() {
+            let signal = (*percent / 100.0) as f64;
+            let code = format.quantize(signal);
+            let nits = pq::code_to_nits(code, format);
+            let value = nits_to_scrgb(nits);
+
+            let x0 = bars_x0 + i as f32 * (bar_width + bar_gap);
+            add_quad(&mut vertices, x0, bar_top, x0 + bar_width, bar_bottom, [value, value, value, 1.0]);
+
+            let text = if *percent < 0.0 {
+                format!("{:+.0}% below black", percent)
+            } else if *percent == 0.0 {
+                format!("Black PQ:{}", code)
+            } else {
+                format!("{:+.0}% PQ:{} {}nits", percent, code, format_nits(nits))
+            };
+            labels.push(HdrTextLabel {
+                text,
+                x: x0 - 0.04,
+                y: bar_bottom - 0.01,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        // Code steps below
+        let top = 0.3f32;
+        let layout = GridLayout {
+            bounds: Rect { y0: top + 0.08, y1: -0.93, ..Rect::FULL },
+            shape: CellShape::Aspect(4.0 / 3.0),
+            gap: Spacing::Percent(2.0),
+            ..GridLayout::default()
+        };
+
+        for (index, cell) in layout.cells(self.count(), ctx.width, ctx.height).iter().enumerate() {
+            let code = self.code(index);
+            let nits = pq::code_to_nits(code, format);
+            let value = nits_to_scrgb(nits);
+
+            add_quad(&mut vertices, cell.rect.x0, cell.rect.y0, cell.rect.x1, cell.rect.y1, [value, value, value, 1.0]);
+
+            labels.push(HdrTextLabel {
+                text: format!("PQ:{} {}nits", code, format_nits(nits)),
+                x: cell.label.x0,
+                y: cell.label.y0,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        labels.push(HdrTextLabel {
+            text: format!(
+                "{} range codes {}-{} step {}  Up/Down: move  Left/Right: step size  +/-: patch count  N: range",
+                match format.range {
+                    Range::Full => "Full",
+                    Range::Narrow => "Narrow",
+                },
+                self.code(0),
+                self.code(self.count() - 1),
+                self.params.int(STEP),
+            ),
+            x: -0.92,
+            y: top + 0.08,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        let (start, step, count) = (self.params.int(START), self.params.int(STEP), self.params.int(COUNT));
+        match key {
+            Key::Named(NamedKey::ArrowUp) => {
+                let last_start = self.format().white_code() as i32 - step * (count - 1);
+                self.params.set(START, (start + step).min(last_start));
+            }
+            Key::Named(NamedKey::ArrowDown) => {
+                self.params.set(START, (start - step).max(0));
+            }
+            Key::Named(NamedKey::ArrowRight) => {
+                if let Some(&next) = STEP_SIZES.iter().find(|&&s| s > step) {
+                    self.params.set(STEP, next);
+                }
+            }
+            Key::Named(NamedKey::ArrowLeft) => {
+                if let Some(&next) = STEP_SIZES.iter().rev().find(|&&s| s < step) {
+                    self.params.set(STEP, next);
+                }
+            }
+            Key::Character(c) if c == "+" || c == "=" => {
+                self.params.set(COUNT, (count + 1).min(MAX_COUNT));
+            }
+            Key::Character(c) if c == "-" => {
+                self.params.set(COUNT, (count - 1).max(1));
+            }
+            Key::Character(c) if c.eq_ignore_ascii_case("n") => {
+                self.params.set(NARROW, !self.params.bool(NARROW));
+                self.params.set(START, self.format().black_code() as i32);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
@@ -0,0 +1,336 @@
+//! Typed page parameters. Pages declare them once, `ui.rs` builds controls
+//! for them, and `AppState` saves their values per page between runs.
+
+use std::marker::PhantomData;
+
+/// Handle to a parameter: the id its value is saved under, typed by the
+/// value so a page can only read it back as what it declared
+pub struct ParamId<T> {
+    pub id: &'static str,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T> ParamId<T> {
+    pub const fn new(id: &'static str) -> Self {
+        Self { id, value: PhantomData }
+    }
+}
+
+impl<T> Clone for ParamId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ParamId<T> {}
+
+pub enum Kind {
+    Float { min: f32, max: f32, logarithmic: bool, suffix: &'static str },
+    Int { min: i32, max: i32 },
+    /// Labels of the options, in index order
+    Choice(Vec<String>),
+    Bool,
+    /// Linear BT.709 RGB, 0..1 per component
+    Color,
+    /// Luminances in nits, each within the range, with at least one entry
+    NitsList { min: f32, max: f32 },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Float(f32),
+    Int(i32),
+    /// Index into the options of a `Kind::Choice`
+    Choice(usize),
+    Bool(bool),
+    Color([f32; 3]),
+    NitsList(Vec<f32>),
+}
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Value::Choice(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<[f32; 3]> for Value {
+    fn from(value: [f32; 3]) -> Self {
+        Value::Color(value)
+    }
+}
+
+impl From<Vec<f32>> for Value {
+    fn from(value: Vec<f32>) -> Self {
+        Value::NitsList(value)
+    }
+}
+
+pub struct Param {
+    /// Stable key used when saving, unique within a page
+    pub id: &'static str,
+    pub label: &'static str,
+    pub kind: Kind,
+    pub value: Value,
+}
+
+impl Param {
+    pub fn float(id: ParamId<f32>, label: &'static str, value: f32, min: f32, max: f32) -> Self {
+        let kind = Kind::Float { min, max, logarithmic: false, suffix: "" };
+        Self { id: id.id, label, kind, value: Value::Float(value) }
+    }
+
+    /// Float slider in nits on a logarithmic scale
+    pub fn nits(id: ParamId<f32>, label: &'static str, value: f32, min: f32, max: f32) -> Self {
+        let kind = Kind::Float { min, max, logarithmic: true, suffix: " nits" };
+        Self { id: id.id, label, kind, value: Value::Float(value) }
+    }
+
+    /// Make a float slider logarithmic, for ranges spanning decades
+    pub fn logarithmic(mut self) -> Self {
+        if let Kind::Float { logarithmic, .. } = &mut self.kind {
+            *logarithmic = true;
+        }
+        self
+    }
+
+    pub fn int(id: ParamId<i32>, label: &'static str, value: i32, min: i32, max: i32) -> Self {
+        Self { id: id.id, label, kind: Kind::Int { min, max }, value: Value::Int(value) }
+    }
+
+    /// One of `options`, e.g. `Mode::ALL.map(Mode::name)`, saved by label
+    pub fn choice(
+        id: ParamId<usize>,
+        label: &'static str,
+        options: impl IntoIterator<Item = impl ToString>,
+        value: usize,
+    ) -> Self {
+        let options = options.into_iter().map(|option| option.to_string()).collect();
+        Self { id: id.id, label, kind: Kind::Choice(options), value: Value::Choice(value) }
+    }
+
+    pub fn bool(id: ParamId<bool>, label: &'static str, value: bool) -> Self {
+        Self { id: id.id, label, kind: Kind::Bool, value: Value::Bool(value) }
+    }
+
+    pub fn color(id: ParamId<[f32; 3]>, label: &'static str, value: [f32; 3]) -> Self {
+        Self { id: id.id, label, kind: Kind::Color, value: Value::Color(value) }
+    }
+
+    pub fn nits_list(id: ParamId<Vec<f32>>, label: &'static str, value: &[f32], min: f32, max: f32) -> Self {
+        Self { id: id.id, label, kind: Kind::NitsList { min, max }, value: Value::NitsList(value.to_vec()) }
+    }
+
+    /// Value as saved to the settings file
+    pub fn to_text(&self) -> String {
+        let join = |values: &[f32]| values.iter().map(f32::to_string).collect::<Vec<_>>().join(",");
+        match (&self.value, &self.kind) {
+            (Value::Float(v), _) => v.to_string(),
+            (Value::Int(v), _) => v.to_string(),
+            (Value::Choice(i), Kind::Choice(options)) => options.get(*i).cloned().unwrap_or_default(),
+            (Value::Choice(i), _) => i.to_string(),
+            (Value::Bool(v), _) => v.to_string(),
+            (Value::Color(rgb), _) => join(rgb),
+            (Value::NitsList(values), _) => join(values),
+        }
+    }
+
+    /// Parse a saved value, keeping the current one if it doesn't fit the kind
+    pub fn set_text(&mut self, text: &str) -> bool {
+        // NaN would pass through `clamp`, so only finite numbers are accepted
+        let float = |s: &str| s.trim().parse::<f32>().ok().filter(|v| v.is_finite());
+        let floats = || text.split(',').map(float).collect::<Option<Vec<_>>>();
+        let value = match &self.kind {
+            Kind::Float { min, max, .. } => float(text).map(|v| Value::Float(v.clamp(*min, *max))),
+            Kind::Int { min, max } => text.parse::<i32>().ok().map(|v| Value::Int(v.clamp(*min, *max))),
+            Kind::Choice(options) => options.iter().position(|o| o == text).map(Value::Choice),
+            Kind::Bool => text.parse::<bool>().ok().map(Value::Bool),
+            Kind::Color => floats().and_then(|v| <[f32; 3]>::try_from(v).ok()).map(Value::Color),
+            Kind::NitsList { min, max } => {
+                floats().map(|v| Value::NitsList(v.into_iter().map(|n| n.clamp(*min, *max)).collect()))
+            }
+        };
+        match value {
+            Some(value) => {
+                self.value = value;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A page's parameters, looked up by handle. Asking for a parameter the
+/// page never declared is a bug in the page and panics.
+pub struct Params(Vec<Param>);
+
+impl Params {
+    pub fn new(params: Vec<Param>) -> Self {
+        Self(params)
+    }
+
+    fn param<T>(&self, id: ParamId<T>) -> &Param {
+        match self.0.iter().find(|p| p.id == id.id) {
+            Some(param) => param,
+            None => panic!("no parameter {:?}", id.id),
+        }
+    }
+
+    fn value<T>(&self, id: ParamId<T>) -> &Value {
+        &self.param(id).value
+    }
+
+    pub fn float(&self, id: ParamId<f32>) -> f32 {
+        match self.value(id) {
+            Value::Float(v) => *v,
+            other => panic!("parameter {:?} is {other:?}, not a float", id.id),
+        }
+    }
+
+    pub fn int(&self, id: ParamId<i32>) -> i32 {
+        match self.value(id) {
+            Value::Int(v) => *v,
+            other => panic!("parameter {:?} is {other:?}, not an int", id.id),
+        }
+    }
+
+    pub fn choice(&self, id: ParamId<usize>) -> usize {
+        match self.value(id) {
+            Value::Choice(v) => *v,
+            other => panic!("parameter {:?} is {other:?}, not a choice", id.id),
+        }
+    }
+
+    pub fn bool(&self, id: ParamId<bool>) -> bool {
+        match self.value(id) {
+            Value::Bool(v) => *v,
+            other => panic!("parameter {:?} is {other:?}, not a bool", id.id),
+        }
+    }
+
+    pub fn color(&self, id: ParamId<[f32; 3]>) -> [f32; 3] {
+        match self.value(id) {
+            Value::Color(v) => *v,
+            other => panic!("parameter {:?} is {other:?}, not a color", id.id),
+        }
+    }
+
+    pub fn nits_list(&self, id: ParamId<Vec<f32>>) -> &[f32] {
+        match self.value(id) {
+            Value::NitsList(v) => v,
+            other => panic!("parameter {:?} is {other:?}, not a nits list", id.id),
+        }
+    }
+
+    /// Replace a value, e.g. from a key handler, which does its own clamping
+    pub fn set<T: Into<Value>>(&mut self, id: ParamId<T>, value: T) {
+        let Some(param) = self.0.iter_mut().find(|p| p.id == id.id) else {
+            panic!("no parameter {:?}", id.id);
+        };
+        param.value = value.into();
+    }
+
+    /// Select the next option of a choice, wrapping around after the last
+    pub fn next_choice(&mut self, id: ParamId<usize>) {
+        let count = match &self.param(id).kind {
+            Kind::Choice(options) => options.len(),
+            _ => panic!("parameter {:?} is not a choice", id.id),
+        };
+        self.set(id, (self.choice(id) + 1) % count);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Param> {
+        self.0.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Param> {
+        self.0.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEVEL: ParamId<f32> = ParamId::new("level");
+    const COUNT: ParamId<i32> = ParamId::new("count");
+    const MODE: ParamId<usize> = ParamId::new("mode");
+    const INVERT: ParamId<bool> = ParamId::new("invert");
+    const TINT: ParamId<[f32; 3]> = ParamId::new("tint");
+    const STEPS: ParamId<Vec<f32>> = ParamId::new("steps");
+
+    #[test]
+    fn text_round_trip() {
+        let mut params = Params::new(vec![
+            Param::nits(LEVEL, "Level", 100.0, 1.0, 10000.0),
+            Param::int(COUNT, "Count", 8, 1, 32),
+            Param::choice(MODE, "Mode", ["Rows", "Cells"], 1),
+            Param::bool(INVERT, "Invert", true),
+            Param::color(TINT, "Tint", [1.0, 0.5, 0.0]),
+            Param::nits_list(STEPS, "Steps", &[0.0, 2.5, 1000.0], 0.0, 10000.0),
+        ]);
+        let saved: Vec<String> = params.iter().map(Param::to_text).collect();
+        assert_eq!(saved, ["100", "8", "Cells", "true", "1,0.5,0", "0,2.5,1000"]);
+
+        for param in params.iter_mut() {
+            let text = param.to_text();
+            param.value = Value::Bool(false);
+            assert!(param.set_text(&text), "{}", param.id);
+        }
+        assert_eq!(params.float(LEVEL), 100.0);
+        assert_eq!(params.choice(MODE), 1);
+        assert_eq!(params.nits_list(STEPS), [0.0, 2.5, 1000.0]);
+    }
+
+    #[test]
+    fn next_choice_wraps() {
+        let mut params = Params::new(vec![Param::choice(MODE, "Mode", ["Rows", "Cells", "Diagonal"], 1)]);
+        params.next_choice(MODE);
+        assert_eq!(params.choice(MODE), 2);
+        params.next_choice(MODE);
+        assert_eq!(params.choice(MODE), 0);
+    }
+
+    #[test]
+    fn saved_values_are_validated() {
+        let mut count = Param::int(COUNT, "Count", 8, 1, 32);
+        assert!(count.set_text("100"));
+        assert_eq!(count.value, Value::Int(32));
+        assert!(!count.set_text("eight"));
+
+        let mut mode = Param::choice(MODE, "Mode", ["Rows", "Cells"], 0);
+        assert!(!mode.set_text("Diagonal"));
+        assert_eq!(mode.value, Value::Choice(0));
+
+        let mut tint = Param::color(TINT, "Tint", [1.0; 3]);
+        assert!(!tint.set_text("1,0"));
+        assert!(!tint.set_text("1,NaN,0"));
+
+        let mut level = Param::float(LEVEL, "Level", 0.5, 0.0, 1.0);
+        assert!(!level.set_text("NaN"));
+        assert!(!level.set_text("inf"));
+        assert_eq!(level.value, Value::Float(0.5));
+
+        // An empty list can't be entered in the panel, so it isn't a saved value
+        let mut steps = Param::nits_list(STEPS, "Steps", &[0.0, 100.0], 0.0, 10000.0);
+        assert!(!steps.set_text(""));
+        assert_eq!(steps.value, Value::NitsList(vec![0.0, 100.0]));
+    }
+}
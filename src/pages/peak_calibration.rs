@@ -0,0 +1,147 @@
+use crate::transfer::{pq, BitDepth, CodeFormat, Range};
+use crate::ui::HdrTextLabel;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageAction, PageOutput, RenderContext, VIEWPORT_ASPECT, add_quad, format_nits, nits_to_scrgb};
+
+/// HGIG-style peak luminance calibration: a patch at a candidate peak sits
+/// inside a brighter surround, and the candidate is raised until the patch
+/// disappears, i.e. the display clips both to the same luminance
+pub struct PeakCalibration {
+    params: Params,
+    /// Candidate last sent as the max brightness
+    applied: Option<u16>,
+}
+
+/// Candidate peak as a 10-bit full range PQ code
+const CANDIDATE: ParamId<i32> = ParamId::new("candidate");
+/// Surround one PQ code above the candidate instead of at the PQ maximum
+/// of 10000 nits
+const NEXT_STEP: ParamId<bool> = ParamId::new("next_step");
+
+const FORMAT: CodeFormat = CodeFormat::new(BitDepth::Ten, Range::Full);
+/// Candidates cover the range of the max brightness slider
+const MIN_CODE: u16 = 520;
+const MAX_CODE: u16 = 1023;
+
+impl Default for PeakCalibration {
+    fn default() -> Self {
+        let candidate = pq::nits_to_code(1000.0, FORMAT) as i32;
+        Self {
+            params: Params::new(vec![
+                Param::int(CANDIDATE, "Candidate code", candidate, MIN_CODE as i32, MAX_CODE as i32),
+                Param::bool(NEXT_STEP, "Surround one code above", false),
+            ]),
+            applied: None,
+        }
+    }
+}
+
+impl PeakCalibration {
+    fn candidate(&self) -> u16 {
+        self.params.int(CANDIDATE) as u16
+    }
+
+    fn candidate_nits(&self) -> f32 {
+        pq::code_to_nits(self.candidate(), FORMAT)
+    }
+
+    fn surround_code(&self) -> u16 {
+        match self.params.bool(NEXT_STEP) {
+            true => (self.candidate() + 1).min(MAX_CODE),
+            false => MAX_CODE,
+        }
+    }
+}
+
+impl Page for PeakCalibration {
+    fn name(&self) -> &'static str {
+        "Peak Luminance Calibration (HGIG)"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 20.0).max(12.0);
+
+        // Square surround 40% of the viewport height, patch a third of that
+        let surround_h = 0.4f32;
+        let surround_w = surround_h / VIEWPORT_ASPECT;
+        let surround = nits_to_scrgb(pq::code_to_nits(self.surround_code(), FORMAT));
+        add_quad(&mut vertices, -surround_w, surround_h, surround_w, -surround_h, [surround, surround, surround, 1.0]);
+
+        let patch_h = surround_h / 3.0;
+        let patch_w = surround_w / 3.0;
+        let patch = nits_to_scrgb(self.candidate_nits());
+        add_quad(&mut vertices, -patch_w, patch_h, patch_w, -patch_h, [patch, patch, patch, 1.0]);
+
+        let surround_text = match self.params.bool(NEXT_STEP) {
+            true => format!("PQ:{}", self.surround_code()),
+            false => "10000nits".to_string(),
+        };
+        let status = if self.applied == Some(self.candidate()) {
+            " - applied".to_string()
+        } else {
+            format!(" (current max {}nits)", format_nits(ctx.max_brightness_nits))
+        };
+
+        let labels = vec![
+            HdrTextLabel {
+                text: format!(
+                    "Candidate PQ:{} {}nits, surround {}{}",
+                    self.candidate(),
+                    format_nits(self.candidate_nits()),
+                    surround_text,
+                    status,
+                ),
+                x: -0.9,
+                y: -0.7,
+                nits: 40.0,
+                size: font_size,
+            },
+            HdrTextLabel {
+                text: "Raise the candidate until the center patch disappears".to_string(),
+                x: -0.9,
+                y: -0.78,
+                nits: 40.0,
+                size: font_size,
+            },
+            HdrTextLabel {
+                text: "Up/Down: 1 code (Shift: 10)  S: surround  Enter: set max brightness".to_string(),
+                x: -0.9,
+                y: -0.86,
+                nits: 40.0,
+                size: font_size,
+            },
+        ];
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, modifiers: ModifiersState) -> Option<PageAction> {
+        let step = if modifiers.shift_key() { 10 } else { 1 };
+        let candidate = self.candidate();
+        match key {
+            Key::Named(NamedKey::ArrowUp) => {
+                self.params.set(CANDIDATE, (candidate + step).min(MAX_CODE) as i32);
+            }
+            Key::Named(NamedKey::ArrowDown) => {
+                self.params.set(CANDIDATE, candidate.saturating_sub(step).max(MIN_CODE) as i32);
+            }
+            Key::Character(c) if c.eq_ignore_ascii_case("s") => {
+                self.params.set(NEXT_STEP, !self.params.bool(NEXT_STEP));
+            }
+            Key::Named(NamedKey::Enter) => {
+                self.applied = Some(candidate);
+                return Some(PageAction::SetMaxBrightness(self.candidate_nits()));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
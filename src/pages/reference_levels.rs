@@ -0,0 +1,107 @@
+use crate::transfer::{hlg, pq};
+use crate::ui::HdrTextLabel;
+use super::layout::{GridLayout, Rect, Spacing};
+use super::{Page, PageOutput, RenderContext, add_quad, format_nits, nits_to_scrgb};
+
+/// ITU-R BT.2408 reference levels, and the same levels relative to the
+/// current paper white so the two can be compared side by side
+pub struct ReferenceLevels;
+
+const REFERENCE_WHITE_NITS: f32 = 203.0;
+/// HLG signal levels in BT.2408 assume a 1000 nit display
+const HLG_PEAK_NITS: f32 = 1000.0;
+
+/// Display luminance with reference white at 203 nits. Skin tones are the
+/// ColorChecker skin patches at reference white.
+const LEVELS: [(&str, f32); 8] = [
+    ("Dark skin", 20.0),
+    ("18% grey card", 26.0),
+    ("Light skin", 68.0),
+    ("83% grey chart", 162.0),
+    ("90% grey chart", 179.0),
+    ("Reference white", REFERENCE_WHITE_NITS),
+    ("Highlight +1 stop", 406.0),
+    ("HLG peak", HLG_PEAK_NITS),
+];
+
+impl Page for ReferenceLevels {
+    fn name(&self) -> &'static str {
+        "BT.2408 Reference Levels"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+
+        let margin = 0.08f32;
+        let title_height = 0.08f32;
+        let line_height = 0.05f32;
+
+        let rows = [
+            (format!("BT.2408: reference white {}nits", format_nits(REFERENCE_WHITE_NITS)), REFERENCE_WHITE_NITS),
+            (format!("Relative to paper white {}nits", format_nits(ctx.paper_white_nits)), ctx.paper_white_nits),
+        ];
+
+        for (row, (title, white_nits)) in rows.iter().enumerate() {
+            // Each row takes half the page, below its title
+            let top = 1.0 - row as f32;
+            labels.push(HdrTextLabel {
+                text: title.clone(),
+                x: -1.0 + margin,
+                y: top - margin,
+                nits: 40.0,
+                size: font_size,
+            });
+
+            let layout = GridLayout {
+                bounds: Rect { y0: top - title_height, y1: top - 1.0, ..Rect::FULL },
+                rows: Some(1),
+                gap: Spacing::Percent(1.5),
+                label_height: Spacing::Percent(8.0),
+                ..GridLayout::default()
+            };
+            let cells = layout.cells(LEVELS.len(), ctx.width, ctx.height);
+
+            for (cell, &(name, reference_nits)) in cells.iter().zip(&LEVELS) {
+                let nits = reference_nits * white_nits / REFERENCE_WHITE_NITS;
+                let name = if row == 1 && reference_nits == REFERENCE_WHITE_NITS { "Paper white" } else { name };
+
+                let value = nits_to_scrgb(nits);
+                add_quad(&mut vertices, cell.rect.x0, cell.rect.y0, cell.rect.x1, cell.rect.y1, [value, value, value, 1.0]);
+
+                let hlg = if nits <= HLG_PEAK_NITS {
+                    format!("{:.0}%", hlg::nits_to_signal(nits, HLG_PEAK_NITS) * 100.0)
+                } else {
+                    "-".to_string()
+                };
+                let lines = [
+                    name.to_string(),
+                    format!("{}nits", format_nits(nits)),
+                    format!("PQ {:.0}% HLG {}", pq::inverse_eotf(nits) * 100.0, hlg),
+                ];
+                for (line, text) in lines.into_iter().enumerate() {
+                    labels.push(HdrTextLabel {
+                        text,
+                        x: cell.label.x0,
+                        y: cell.label.y0 - line as f32 * line_height,
+                        nits: 40.0,
+                        size: font_size,
+                    });
+                }
+            }
+        }
+
+        labels.push(HdrTextLabel {
+            text: format!("HLG signals for a {}nit display", format_nits(HLG_PEAK_NITS)),
+            x: -1.0 + margin,
+            y: -1.0 + margin * 0.6,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        PageOutput { vertices, labels }
+    }
+}
@@ -1,20 +1,31 @@
 use crate::ui::HdrTextLabel;
-use super::{Page, PageOutput, add_quad};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageOutput, RenderContext, add_quad};
 
-pub struct SplitCompare;
+pub struct SplitCompare {
+    params: Params,
+}
+
+const BANDS: ParamId<i32> = ParamId::new("bands");
+
+impl Default for SplitCompare {
+    fn default() -> Self {
+        Self { params: Params::new(vec![Param::int(BANDS, "Bands", 8, 1, 32)]) }
+    }
+}
 
 impl Page for SplitCompare {
     fn name(&self) -> &'static str {
         "Split Compare (SDR | HDR)"
     }
 
-    fn render(&self, width: u32, height: u32, max_brightness_nits: f32, _time: f32) -> PageOutput {
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
         let mut vertices = Vec::new();
 
-        let scale = height.min(width) as f32 / 1080.0;
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
         let font_size = (scale * 24.0).max(14.0);
-        let max_scrgb = max_brightness_nits / 80.0;
-        let bands = 8;
+        let max_scrgb = ctx.max_brightness_nits / 80.0;
+        let bands = self.params.int(BANDS);
         let band_height = 2.0 / bands as f32;
 
         for i in 0..bands {
@@ -51,4 +62,8 @@ impl Page for SplitCompare {
 
         PageOutput { vertices, labels }
     }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
 }
@@ -0,0 +1,246 @@
+use crate::ui::HdrTextLabel;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::params::{Param, ParamId, Params};
+use super::window_pattern::{WINDOW_SIZES, step_window_size, window_half_extents, window_size_param};
+use super::{Page, PageAction, PageOutput, RenderContext, add_quad, format_nits, nits_to_scrgb};
+
+/// Timed window pattern for watching ABL dim a panel over time. Each run
+/// shows the window for `duration` seconds, then black for `cooldown`
+/// seconds, and appends its wall-clock timing to a CSV log so meter
+/// readings can be lined up with the moment the pattern started.
+pub struct SustainedBrightness {
+    params: Params,
+    pub log_path: PathBuf,
+    run: Option<Run>,
+    runs_started: u32,
+    last_error: Option<String>,
+}
+
+const WINDOW: ParamId<usize> = ParamId::new("window");
+const NITS: ParamId<f32> = ParamId::new("nits");
+/// Seconds the window is shown
+const DURATION: ParamId<f32> = ParamId::new("duration");
+/// Seconds of black after the window
+const COOLDOWN: ParamId<f32> = ParamId::new("cooldown");
+
+/// Settings of one run, fixed when it starts so the log matches what was shown
+struct Run {
+    index: u32,
+    area_percent: f32,
+    nits: f32,
+    duration: f32,
+    cooldown: f32,
+    started: Instant,
+    started_unix: f64,
+    /// Unix time the window was replaced by black
+    pattern_end_unix: Option<f64>,
+}
+
+impl Default for SustainedBrightness {
+    fn default() -> Self {
+        Self {
+            params: Params::new(vec![
+                window_size_param(WINDOW),
+                Param::nits(NITS, "Window", 1000.0, 1.0, 10000.0),
+                Param::float(DURATION, "Duration (seconds)", 60.0, 5.0, 600.0),
+                Param::float(COOLDOWN, "Cooldown (seconds)", 30.0, 0.0, 600.0),
+            ]),
+            log_path: PathBuf::from("sustained_brightness.csv"),
+            run: None,
+            runs_started: 0,
+            last_error: None,
+        }
+    }
+}
+
+fn unix_seconds() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+impl SustainedBrightness {
+    fn start_run(&mut self) {
+        self.runs_started += 1;
+        self.run = Some(Run {
+            index: self.runs_started,
+            area_percent: WINDOW_SIZES[self.params.choice(WINDOW)],
+            nits: self.params.float(NITS),
+            duration: self.params.float(DURATION),
+            cooldown: self.params.float(COOLDOWN),
+            started: Instant::now(),
+            started_unix: unix_seconds(),
+            pattern_end_unix: None,
+        });
+    }
+
+    fn toggle_run(&mut self) {
+        if self.run.is_some() {
+            self.finish_run(true);
+        } else {
+            self.start_run();
+        }
+    }
+
+    /// End the current run and append it to the log
+    fn finish_run(&mut self, aborted: bool) {
+        let Some(run) = self.run.take() else {
+            return;
+        };
+        let now = unix_seconds();
+        let pattern_end = run.pattern_end_unix.unwrap_or(now);
+
+        if let Err(e) = self.append_log(&run, pattern_end, now, aborted) {
+            eprintln!("Failed to write {}: {}", self.log_path.display(), e);
+            self.last_error = Some(e.to_string());
+        } else {
+            self.last_error = None;
+        }
+    }
+
+    fn append_log(&self, run: &Run, pattern_end: f64, run_end: f64, aborted: bool) -> std::io::Result<()> {
+        let is_new = !self.log_path.exists();
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.log_path)?;
+        if is_new {
+            writeln!(
+                file,
+                "run,area_percent,nits,duration_s,cooldown_s,pattern_start_unix,pattern_end_unix,cooldown_end_unix,aborted"
+            )?;
+        }
+        writeln!(
+            file,
+            "{},{},{},{},{},{:.3},{:.3},{:.3},{}",
+            run.index,
+            run.area_percent,
+            run.nits,
+            run.duration,
+            run.cooldown,
+            run.started_unix,
+            pattern_end,
+            run_end,
+            aborted,
+        )
+    }
+}
+
+impl Page for SustainedBrightness {
+    fn name(&self) -> &'static str {
+        "Sustained Brightness (ABL over time)"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 20.0).max(12.0);
+
+        let status = match &self.run {
+            Some(run) => {
+                let elapsed = run.started.elapsed().as_secs_f32();
+                if elapsed < run.duration {
+                    let (half_w, half_h) = window_half_extents(run.area_percent);
+                    let value = nits_to_scrgb(run.nits);
+                    add_quad(&mut vertices, -half_w, half_h, half_w, -half_h, [value, value, value, 1.0]);
+                    format!("Run {}: {:.1}s / {:.0}s", run.index, elapsed, run.duration)
+                } else {
+                    format!("Run {}: cooldown {:.1}s / {:.0}s", run.index, elapsed - run.duration, run.cooldown)
+                }
+            }
+            None => "Enter: start run".to_string(),
+        };
+
+        // Settings of the run in progress, or of the next one
+        let (area_percent, nits, duration, cooldown) = match &self.run {
+            Some(run) => (run.area_percent, run.nits, run.duration, run.cooldown),
+            None => (
+                WINDOW_SIZES[self.params.choice(WINDOW)],
+                self.params.float(NITS),
+                self.params.float(DURATION),
+                self.params.float(COOLDOWN),
+            ),
+        };
+
+        let mut labels = vec![
+            HdrTextLabel {
+                text: status,
+                x: -0.95,
+                y: 0.95,
+                nits: 40.0,
+                size: font_size,
+            },
+            HdrTextLabel {
+                text: format!(
+                    "{}% window {}nits, {:.0}s on, {:.0}s off",
+                    area_percent,
+                    format_nits(nits),
+                    duration,
+                    cooldown,
+                ),
+                x: -0.95,
+                y: -0.9,
+                nits: 40.0,
+                size: font_size,
+            },
+        ];
+
+        if let Some(error) = &self.last_error {
+            labels.push(HdrTextLabel {
+                text: format!("Log error: {}", error),
+                x: -0.95,
+                y: 0.87,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        PageOutput { vertices, labels }
+    }
+
+    fn update(&mut self) {
+        let Some(run) = &mut self.run else {
+            return;
+        };
+        let elapsed = run.started.elapsed().as_secs_f32();
+        if elapsed >= run.duration && run.pattern_end_unix.is_none() {
+            run.pattern_end_unix = Some(unix_seconds());
+        }
+        if elapsed >= run.duration + run.cooldown {
+            self.finish_run(false);
+        }
+    }
+
+    fn on_hide(&mut self) {
+        self.finish_run(true);
+    }
+
+    fn on_key(&mut self, key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        // Changes apply from the next run; the current one keeps its settings
+        let nits = self.params.float(NITS);
+        match key {
+            Key::Named(NamedKey::Enter) => self.toggle_run(),
+            Key::Named(NamedKey::ArrowRight) => step_window_size(&mut self.params, WINDOW, true),
+            Key::Named(NamedKey::ArrowLeft) => step_window_size(&mut self.params, WINDOW, false),
+            Key::Named(NamedKey::ArrowUp) => self.params.set(NITS, (nits * 2.0).min(10000.0)),
+            Key::Named(NamedKey::ArrowDown) => self.params.set(NITS, (nits / 2.0).max(1.0)),
+            _ => {}
+        }
+        None
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        let button = if self.run.is_some() { "Abort run" } else { "Start run" };
+        if ui.button(button).clicked() {
+            self.toggle_run();
+        }
+        ui.label(format!("Log: {}", self.log_path.display()));
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
@@ -0,0 +1,178 @@
+use crate::tonemap::Operator;
+use crate::transfer::pq;
+use crate::ui::HdrTextLabel;
+use winit::keyboard::{Key, ModifiersState};
+use super::geometry::{Canvas, add_gradient_quad_h, add_pixel_line};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageAction, PageOutput, RenderContext, format_nits, nits_to_scrgb};
+
+/// Plots the selected tone-mapping curve for 4000 and 10000 nit masters on
+/// the current display peak, next to ramps sent untouched and through it
+pub struct ToneCurves {
+    params: Params,
+}
+
+const OPERATOR: ParamId<usize> = ParamId::new("operator");
+
+impl Default for ToneCurves {
+    fn default() -> Self {
+        Self { params: Params::new(vec![Param::choice(OPERATOR, "Operator", Operator::ALL.map(Operator::name), 0)]) }
+    }
+}
+
+const SOURCE_PEAKS: [f32; 2] = [4000.0, 10000.0];
+/// Curve colors per source peak, in scRGB
+const CURVE_COLORS: [[f32; 4]; 2] = [[2.5, 1.5, 0.2, 1.0], [0.3, 1.5, 2.5, 1.0]];
+const PLOT_TICKS: [f32; 5] = [1.0, 10.0, 100.0, 1000.0, 10000.0];
+
+const CURVE_SEGMENTS: usize = 64;
+const RAMP_SEGMENTS: usize = 64;
+/// Line width in pixels at 1080p
+const LINE_PIXELS: f32 = 2.0;
+
+impl ToneCurves {
+    fn operator(&self) -> Operator {
+        Operator::ALL[self.params.choice(OPERATOR)]
+    }
+}
+
+impl Page for ToneCurves {
+    fn name(&self) -> &'static str {
+        "Tone Mapping Curves"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+        let canvas = Canvas::new(ctx.width, ctx.height);
+        let line = (LINE_PIXELS * scale).max(1.0);
+        let operator = self.operator();
+
+        // Curve plot on the left, PQ-encoded input against PQ-encoded output
+        let (px0, py0, px1, py1) = (-0.85f32, -0.8f32, -0.1f32, 0.8f32);
+        let to_plot = |input_nits: f32, output_nits: f32| {
+            [
+                px0 + pq::inverse_eotf(input_nits) * (px1 - px0),
+                py0 + pq::inverse_eotf(output_nits) * (py1 - py0),
+            ]
+        };
+
+        let axis = [nits_to_scrgb(40.0), nits_to_scrgb(40.0), nits_to_scrgb(40.0), 1.0];
+        let guide = [nits_to_scrgb(10.0), nits_to_scrgb(10.0), nits_to_scrgb(10.0), 1.0];
+        add_pixel_line(&mut vertices, &canvas, [px0, py0], [px1, py0], line, axis);
+        add_pixel_line(&mut vertices, &canvas, [px0, py0], [px0, py1], line, axis);
+
+        for tick in PLOT_TICKS {
+            let [x, y] = to_plot(tick, tick);
+            add_pixel_line(&mut vertices, &canvas, [x, py0], [x, py1], (line * 0.5).max(1.0), guide);
+            add_pixel_line(&mut vertices, &canvas, [px0, y], [px1, y], (line * 0.5).max(1.0), guide);
+            labels.push(HdrTextLabel {
+                text: format_nits(tick),
+                x: x - 0.02,
+                y: py0 - 0.02,
+                nits: 40.0,
+                size: font_size,
+            });
+            labels.push(HdrTextLabel {
+                text: format_nits(tick),
+                x: px0 - 0.09,
+                y: y + 0.02,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        // Identity and the display's hard clip for reference
+        add_pixel_line(&mut vertices, &canvas, to_plot(0.0, 0.0), to_plot(10000.0, 10000.0), line, axis);
+        let clip_y = to_plot(0.0, ctx.max_brightness_nits)[1];
+        add_pixel_line(&mut vertices, &canvas, [px0, clip_y], [px1, clip_y], line, axis);
+        labels.push(HdrTextLabel {
+            text: format!("Display peak {}nits", format_nits(ctx.max_brightness_nits)),
+            x: px0 + 0.02,
+            y: clip_y + 0.05,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        for (source_peak, color) in SOURCE_PEAKS.into_iter().zip(CURVE_COLORS) {
+            let source_max = pq::inverse_eotf(source_peak);
+            let mut last = to_plot(0.0, 0.0);
+            for seg in 1..=CURVE_SEGMENTS {
+                let input = pq::eotf(seg as f32 / CURVE_SEGMENTS as f32 * source_max);
+                let output = operator.apply(input, source_peak, ctx.max_brightness_nits);
+                let point = to_plot(input, output);
+                add_pixel_line(&mut vertices, &canvas, last, point, line * 1.5, color);
+                last = point;
+            }
+        }
+
+        labels.push(HdrTextLabel {
+            text: format!("{} (input vs output, PQ scale)  O: next operator", operator.name()),
+            x: px0,
+            y: py1 + 0.1,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        // Ramps on the right, PQ-uniform from black to each source peak
+        let (rx0, rx1) = (0.05f32, 0.95f32);
+        let ramp_height = 0.25f32;
+        let ramp_gap = 0.15f32;
+        let mut y0 = 0.8f32;
+
+        for source_peak in SOURCE_PEAKS {
+            let source_max = pq::inverse_eotf(source_peak);
+            let ramps = [
+                (format!("{} nit master, untouched", source_peak), None),
+                (format!("{} nit master, {}", source_peak, operator.name()), Some(operator)),
+            ];
+
+            for (title, operator) in ramps {
+                let y1 = y0 - ramp_height;
+                for seg in 0..RAMP_SEGMENTS {
+                    let t0 = seg as f32 / RAMP_SEGMENTS as f32;
+                    let t1 = (seg + 1) as f32 / RAMP_SEGMENTS as f32;
+                    let x0 = rx0 + t0 * (rx1 - rx0);
+                    let x1 = rx0 + t1 * (rx1 - rx0);
+
+                    let [n0, n1] = [t0, t1].map(|t| {
+                        let nits = pq::eotf(t * source_max);
+                        match operator {
+                            Some(op) => op.apply(nits, source_peak, ctx.max_brightness_nits),
+                            None => nits,
+                        }
+                    });
+                    let v0 = nits_to_scrgb(n0);
+                    let v1 = nits_to_scrgb(n1);
+                    add_gradient_quad_h(&mut vertices, x0, y0, x1, y1, [v0, v0, v0, 1.0], [v1, v1, v1, 1.0]);
+                }
+
+                labels.push(HdrTextLabel {
+                    text: title,
+                    x: rx0,
+                    y: y1 - 0.01,
+                    nits: 40.0,
+                    size: font_size,
+                });
+                y0 = y1 - ramp_gap;
+            }
+        }
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        match key {
+            Key::Character(c) if c.eq_ignore_ascii_case("o") => self.params.next_choice(OPERATOR),
+            _ => {}
+        }
+        None
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
@@ -0,0 +1,257 @@
+use crate::dx12::letterbox_viewport;
+use crate::ui::HdrTextLabel;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::geometry::{Canvas, add_pixel_line};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageAction, PageOutput, RenderContext, add_quad, format_nits, nits_to_scrgb};
+
+/// Full-field patch with a grid of measurement targets, for building a
+/// screen uniformity map with a meter
+pub struct Uniformity {
+    params: Params,
+    pub export_path: PathBuf,
+    /// Window size, for target pixel positions on export
+    window_size: Option<(u32, u32)>,
+    export_status: Option<String>,
+}
+
+/// Whether the field follows `PERCENT` of the current max brightness or
+/// `NITS`, in `LEVEL_MODES` order
+const LEVEL_MODE: ParamId<usize> = ParamId::new("level_mode");
+const PERCENT: ParamId<f32> = ParamId::new("percent");
+const NITS: ParamId<f32> = ParamId::new("nits");
+/// Targets per side, one of `GRIDS`
+const GRID: ParamId<usize> = ParamId::new("grid");
+
+const LEVEL_MODES: &[&str] = &["% of peak", "Absolute nits"];
+
+#[derive(Clone, Copy, PartialEq)]
+enum FieldLevel {
+    /// Percentage of the current max brightness
+    Relative(f32),
+    /// Absolute luminance in nits
+    Absolute(f32),
+}
+
+impl FieldLevel {
+    fn nits(self, max_brightness_nits: f32) -> f32 {
+        match self {
+            FieldLevel::Relative(percent) => max_brightness_nits * percent / 100.0,
+            FieldLevel::Absolute(nits) => nits,
+        }
+    }
+}
+
+const LEVELS: [FieldLevel; 6] = [
+    FieldLevel::Relative(5.0),
+    FieldLevel::Relative(18.0),
+    FieldLevel::Relative(50.0),
+    FieldLevel::Relative(100.0),
+    FieldLevel::Absolute(100.0),
+    FieldLevel::Absolute(203.0),
+];
+const GRIDS: [u32; 3] = [3, 5, 9];
+/// Outline luminance relative to the field, so targets stay visible but dim
+const OUTLINE_SCALE: f32 = 0.5;
+const OUTLINE_PIXELS: f32 = 2.0;
+
+impl Default for Uniformity {
+    fn default() -> Self {
+        Self {
+            params: Params::new(vec![
+                Param::choice(LEVEL_MODE, "Level", LEVEL_MODES, 0),
+                Param::float(PERCENT, "Percent of peak", 18.0, 1.0, 100.0),
+                Param::nits(NITS, "Absolute", 203.0, 1.0, 10000.0),
+                Param::choice(GRID, "Targets", GRIDS.map(|n| format!("{n}x{n}")), 1),
+            ]),
+            export_path: PathBuf::from("uniformity_targets.csv"),
+            window_size: None,
+            export_status: None,
+        }
+    }
+}
+
+impl Uniformity {
+    fn level(&self) -> FieldLevel {
+        match self.params.choice(LEVEL_MODE) {
+            0 => FieldLevel::Relative(self.params.float(PERCENT)),
+            _ => FieldLevel::Absolute(self.params.float(NITS)),
+        }
+    }
+
+    fn set_level(&mut self, level: FieldLevel) {
+        match level {
+            FieldLevel::Relative(percent) => {
+                self.params.set(LEVEL_MODE, 0);
+                self.params.set(PERCENT, percent);
+            }
+            FieldLevel::Absolute(nits) => {
+                self.params.set(LEVEL_MODE, 1);
+                self.params.set(NITS, nits);
+            }
+        }
+    }
+
+    fn grid(&self) -> u32 {
+        GRIDS[self.params.choice(GRID)]
+    }
+
+    /// Target centers in NDC, in raster order
+    fn target_centers(&self) -> Vec<[f32; 2]> {
+        let n = self.grid();
+        (0..n * n)
+            .map(|i| {
+                let (row, col) = (i / n, i % n);
+                [
+                    -1.0 + (col as f32 + 0.5) * 2.0 / n as f32,
+                    1.0 - (row as f32 + 0.5) * 2.0 / n as f32,
+                ]
+            })
+            .collect()
+    }
+
+    fn export(&mut self) {
+        let result = self.write_targets();
+        self.export_status = Some(match result {
+            Ok(()) => format!("Exported {}", self.export_path.display()),
+            Err(e) => {
+                eprintln!("Failed to write {}: {}", self.export_path.display(), e);
+                format!("Export failed: {}", e)
+            }
+        });
+    }
+
+    fn write_targets(&self) -> std::io::Result<()> {
+        let Some((width, height)) = self.window_size else {
+            return Err(std::io::Error::other("window size not known yet"));
+        };
+        let (vp_width, vp_height, vp_x, vp_y) = letterbox_viewport(width, height);
+        let grid = self.grid();
+
+        let mut file = File::create(&self.export_path)?;
+        writeln!(file, "index,row,col,ndc_x,ndc_y,pixel_x,pixel_y")?;
+        for (i, [x, y]) in self.target_centers().into_iter().enumerate() {
+            let pixel_x = vp_x + (x + 1.0) * 0.5 * vp_width;
+            let pixel_y = vp_y + (1.0 - y) * 0.5 * vp_height;
+            writeln!(
+                file,
+                "{},{},{},{:.4},{:.4},{:.1},{:.1}",
+                i + 1,
+                i as u32 / grid + 1,
+                i as u32 % grid + 1,
+                x,
+                y,
+                pixel_x,
+                pixel_y,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Page for Uniformity {
+    fn name(&self) -> &'static str {
+        "Screen Uniformity"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+        let mut labels = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 16.0).max(12.0);
+
+        let level = self.level();
+        let grid = self.grid();
+        let nits = level.nits(ctx.max_brightness_nits);
+        let value = nits_to_scrgb(nits);
+        add_quad(&mut vertices, -1.0, 1.0, 1.0, -1.0, [value, value, value, 1.0]);
+
+        // Target boxes are square in pixels, a quarter of the cell height
+        let canvas = Canvas::new(ctx.width, ctx.height);
+        let half_h = 0.25 * 2.0 / grid as f32;
+        let half_w = half_h * canvas.height / canvas.width;
+        // Box edges sit inside the box, half a line in from its border
+        let inset_w = OUTLINE_PIXELS / canvas.width;
+        let inset_h = OUTLINE_PIXELS / canvas.height;
+        let outline = value * OUTLINE_SCALE;
+        let color = [outline, outline, outline, 1.0];
+
+        for (i, [x, y]) in self.target_centers().into_iter().enumerate() {
+            let (x0, x1, y0, y1) = (x - half_w, x + half_w, y + half_h, y - half_h);
+            let mut line = |from, to| add_pixel_line(&mut vertices, &canvas, from, to, OUTLINE_PIXELS, color);
+            // Box
+            line([x0, y0 - inset_h], [x1, y0 - inset_h]);
+            line([x0, y1 + inset_h], [x1, y1 + inset_h]);
+            line([x0 + inset_w, y0], [x0 + inset_w, y1]);
+            line([x1 - inset_w, y0], [x1 - inset_w, y1]);
+            // Crosshair
+            line([x0, y], [x1, y]);
+            line([x, y0], [x, y1]);
+
+            labels.push(HdrTextLabel {
+                text: format!("{}", i + 1),
+                x: x0,
+                y: y0 + 0.05,
+                nits: 40.0,
+                size: font_size,
+            });
+        }
+
+        let level = match level {
+            FieldLevel::Relative(percent) => format!("{}% of peak = {}nits", percent, format_nits(nits)),
+            FieldLevel::Absolute(_) => format!("{}nits", format_nits(nits)),
+        };
+        let mut status = format!("{}  {}x{} targets  Up/Down: level  1-3: grid  E: export", level, grid, grid);
+        if let Some(export_status) = &self.export_status {
+            status = format!("{}  [{}]", status, export_status);
+        }
+        labels.push(HdrTextLabel {
+            text: status,
+            x: -0.98,
+            y: -0.94,
+            nits: 40.0,
+            size: font_size,
+        });
+
+        PageOutput { vertices, labels }
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        self.window_size = Some((width, height));
+    }
+
+    fn on_key(&mut self, key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        let level_index = LEVELS.iter().position(|&l| l == self.level());
+        match key {
+            Key::Named(NamedKey::ArrowUp) => {
+                self.set_level(LEVELS[level_index.map_or(0, |i| (i + 1) % LEVELS.len())]);
+            }
+            Key::Named(NamedKey::ArrowDown) => {
+                self.set_level(LEVELS[level_index.map_or(0, |i| (i + LEVELS.len() - 1) % LEVELS.len())]);
+            }
+            Key::Character(c) if c == "1" => self.params.set(GRID, 0),
+            Key::Character(c) if c == "2" => self.params.set(GRID, 1),
+            Key::Character(c) if c == "3" => self.params.set(GRID, 2),
+            Key::Character(c) if c.eq_ignore_ascii_case("e") => self.export(),
+            _ => {}
+        }
+        None
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        if ui.button("Export target positions").clicked() {
+            self.export();
+        }
+        if let Some(status) = &self.export_status {
+            ui.label(status);
+        }
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::ui::HdrTextLabel;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageAction, PageOutput, RenderContext, VIEWPORT_ASPECT, add_quad, format_nits, nits_to_scrgb};
+
+/// Centered white window on black covering a percentage of the screen, for
+/// measuring ABL and peak brightness against window size
+pub struct WindowPattern {
+    params: Params,
+}
+
+const WINDOW: ParamId<usize> = ParamId::new("window");
+const NITS: ParamId<f32> = ParamId::new("nits");
+
+/// Window areas as a percentage of the viewport
+pub const WINDOW_SIZES: [f32; 8] = [1.0, 2.0, 5.0, 10.0, 18.0, 25.0, 50.0, 100.0];
+const NITS_LEVELS: [f32; 11] = [
+    100.0, 203.0, 400.0, 600.0, 800.0, 1000.0, 1500.0, 2000.0, 3000.0, 4000.0, 10000.0,
+];
+
+impl Default for WindowPattern {
+    fn default() -> Self {
+        Self {
+            params: Params::new(vec![
+                window_size_param(WINDOW),
+                Param::nits(NITS, "Window", 1000.0, 1.0, 10000.0),
+            ]),
+        }
+    }
+}
+
+/// Choice of `WINDOW_SIZES`, starting at 10%
+pub fn window_size_param(id: ParamId<usize>) -> Param {
+    Param::choice(id, "Window size", WINDOW_SIZES.map(|size| format!("{size}%")), 3)
+}
+
+/// Move a window size choice to the next larger or smaller size, stopping
+/// at the ends
+pub fn step_window_size(params: &mut Params, id: ParamId<usize>, larger: bool) {
+    let index = params.choice(id);
+    let index = if larger { (index + 1).min(WINDOW_SIZES.len() - 1) } else { index.saturating_sub(1) };
+    params.set(id, index);
+}
+
+/// Half extents in NDC of a centered window covering `area_percent` of the
+/// viewport. The window is square until it reaches the full viewport
+/// height, then widens so the area stays exact.
+pub fn window_half_extents(area_percent: f32) -> (f32, f32) {
+    let fraction = (area_percent / 100.0).clamp(0.0, 1.0);
+    // Side of the square in units of viewport height
+    let side = (fraction * VIEWPORT_ASPECT).sqrt();
+    if side <= 1.0 {
+        (side / VIEWPORT_ASPECT, side)
+    } else {
+        (fraction, 1.0)
+    }
+}
+
+impl Page for WindowPattern {
+    fn name(&self) -> &'static str {
+        "APL Window"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 20.0).max(12.0);
+
+        let area_percent = WINDOW_SIZES[self.params.choice(WINDOW)];
+        let nits = self.params.float(NITS);
+        let (half_w, half_h) = window_half_extents(area_percent);
+        let value = nits_to_scrgb(nits);
+        add_quad(&mut vertices, -half_w, half_h, half_w, -half_h, [value, value, value, 1.0]);
+
+        let labels = vec![HdrTextLabel {
+            text: format!(
+                "{}% window {}nits  Left/Right: window size  Up/Down: luminance",
+                area_percent,
+                format_nits(nits)
+            ),
+            x: -0.95,
+            y: -0.9,
+            nits: 40.0,
+            size: font_size,
+        }];
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        let nits = self.params.float(NITS);
+        match key {
+            Key::Named(NamedKey::ArrowRight) => step_window_size(&mut self.params, WINDOW, true),
+            Key::Named(NamedKey::ArrowLeft) => step_window_size(&mut self.params, WINDOW, false),
+            Key::Named(NamedKey::ArrowUp) => {
+                if let Some(&next) = NITS_LEVELS.iter().find(|&&n| n > nits) {
+                    self.params.set(NITS, next);
+                }
+            }
+            Key::Named(NamedKey::ArrowDown) => {
+                if let Some(&next) = NITS_LEVELS.iter().rev().find(|&&n| n < nits) {
+                    self.params.set(NITS, next);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
@@ -0,0 +1,204 @@
+use crate::ui::HdrTextLabel;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use super::params::{Param, ParamId, Params};
+use super::{Page, PageAction, PageOutput, RenderContext, add_quad, format_nits, nits_to_scrgb};
+
+/// Lights one grid cell or row at a time over black, so a backlight's
+/// dimming zones can be counted and mapped from the halo around it.
+/// Sweeps are a pure function of page time and the step number is shown on
+/// screen, so separate recordings can be lined up frame by frame.
+pub struct ZoneGrid {
+    params: Params,
+    /// Lit cell in manual mode, in raster order
+    manual_cell: u32,
+}
+
+const MODE: ParamId<usize> = ParamId::new("mode");
+const COLS: ParamId<i32> = ParamId::new("cols");
+const ROWS: ParamId<i32> = ParamId::new("rows");
+const NITS: ParamId<f32> = ParamId::new("nits");
+/// Sweep steps per second
+const RATE: ParamId<f32> = ParamId::new("rate");
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SweepMode {
+    /// One cell at a time in raster order
+    Cells,
+    /// One full row at a time, top to bottom
+    Rows,
+    /// Cell chosen with the arrow keys
+    Manual,
+}
+
+impl SweepMode {
+    const ALL: [SweepMode; 3] = [SweepMode::Cells, SweepMode::Rows, SweepMode::Manual];
+
+    fn name(self) -> &'static str {
+        match self {
+            SweepMode::Cells => "Cell sweep",
+            SweepMode::Rows => "Row sweep",
+            SweepMode::Manual => "Manual",
+        }
+    }
+}
+
+/// Grid resolutions matching the 16:9 viewport
+const GRIDS: [(u32, u32); 6] = [(16, 9), (32, 18), (48, 27), (64, 36), (96, 54), (128, 72)];
+
+impl Default for ZoneGrid {
+    fn default() -> Self {
+        Self {
+            params: Params::new(vec![
+                Param::choice(MODE, "Mode", SweepMode::ALL.map(SweepMode::name), 0),
+                Param::int(COLS, "Columns", 32, 1, 256),
+                Param::int(ROWS, "Rows", 18, 1, 144),
+                Param::nits(NITS, "Cell", 1000.0, 1.0, 10000.0),
+                Param::float(RATE, "Steps per second", 4.0, 0.125, 60.0).logarithmic(),
+            ]),
+            manual_cell: 0,
+        }
+    }
+}
+
+impl ZoneGrid {
+    fn mode(&self) -> SweepMode {
+        SweepMode::ALL[self.params.choice(MODE)]
+    }
+
+    fn grid(&self) -> (u32, u32) {
+        (self.params.int(COLS) as u32, self.params.int(ROWS) as u32)
+    }
+
+    fn step_count(&self) -> u32 {
+        let (cols, rows) = self.grid();
+        match self.mode() {
+            SweepMode::Rows => rows,
+            SweepMode::Cells | SweepMode::Manual => cols * rows,
+        }
+    }
+
+    fn step_at(&self, time: f64) -> u32 {
+        let rate = self.params.float(RATE) as f64;
+        match self.mode() {
+            SweepMode::Manual => self.manual_cell.min(self.step_count() - 1),
+            SweepMode::Cells | SweepMode::Rows => ((time * rate).max(0.0) as u64 % self.step_count() as u64) as u32,
+        }
+    }
+
+    fn set_grid(&mut self, (cols, rows): (u32, u32)) {
+        self.params.set(COLS, cols as i32);
+        self.params.set(ROWS, rows as i32);
+        self.manual_cell = self.manual_cell.min(cols * rows - 1);
+    }
+}
+
+impl Page for ZoneGrid {
+    fn name(&self) -> &'static str {
+        "Dimming Zone Grid"
+    }
+
+    fn render(&self, ctx: &RenderContext) -> PageOutput {
+        let mut vertices = Vec::new();
+
+        let scale = ctx.height.min(ctx.width) as f32 / 1080.0;
+        let font_size = (scale * 14.0).max(10.0);
+
+        let (cols, rows) = self.grid();
+        let nits = self.params.float(NITS);
+        let cell_w = 2.0 / cols as f32;
+        let cell_h = 2.0 / rows as f32;
+        let value = nits_to_scrgb(nits);
+        let color = [value, value, value, 1.0];
+
+        let time = ctx.clock.time();
+        let step = self.step_at(time);
+        let position = match self.mode() {
+            SweepMode::Rows => {
+                let y0 = 1.0 - step as f32 * cell_h;
+                add_quad(&mut vertices, -1.0, y0, 1.0, y0 - cell_h, color);
+                format!("row {}", step + 1)
+            }
+            SweepMode::Cells | SweepMode::Manual => {
+                let row = step / cols;
+                let col = step % cols;
+                let x0 = -1.0 + col as f32 * cell_w;
+                let y0 = 1.0 - row as f32 * cell_h;
+                add_quad(&mut vertices, x0, y0, x0 + cell_w, y0 - cell_h, color);
+                format!("row {} col {}", row + 1, col + 1)
+            }
+        };
+
+        let labels = vec![
+            HdrTextLabel {
+                text: format!(
+                    "{} {}x{} {}nits  step {}/{} ({})  t={:.2}s",
+                    self.mode().name(),
+                    cols,
+                    rows,
+                    format_nits(nits),
+                    step + 1,
+                    self.step_count(),
+                    position,
+                    time,
+                ),
+                x: -0.98,
+                y: -0.94,
+                nits: 40.0,
+                size: font_size,
+            },
+            HdrTextLabel {
+                text: "1-3: mode  +/-: grid  Arrows: rate/nits, or move cell in manual".to_string(),
+                x: -0.98,
+                y: -0.88,
+                nits: 40.0,
+                size: font_size,
+            },
+        ];
+
+        PageOutput { vertices, labels }
+    }
+
+    fn on_key(&mut self, key: &Key, _modifiers: ModifiersState) -> Option<PageAction> {
+        let (cols, rows) = self.grid();
+        let grid_index = GRIDS.iter().position(|&g| g == (cols, rows));
+        let count = cols * rows;
+        let manual = self.mode() == SweepMode::Manual;
+        let nits = self.params.float(NITS);
+        let rate = self.params.float(RATE);
+        match key {
+            Key::Character(c) if c == "1" => self.params.set(MODE, 0),
+            Key::Character(c) if c == "2" => self.params.set(MODE, 1),
+            Key::Character(c) if c == "3" => self.params.set(MODE, 2),
+            Key::Character(c) if c == "+" || c == "=" => {
+                let next = grid_index.map_or(1, |i| (i + 1).min(GRIDS.len() - 1));
+                self.set_grid(GRIDS[next]);
+            }
+            Key::Character(c) if c == "-" => {
+                let prev = grid_index.map_or(1, |i| i.saturating_sub(1));
+                self.set_grid(GRIDS[prev]);
+            }
+            Key::Named(NamedKey::ArrowRight) if manual => {
+                self.manual_cell = (self.manual_cell + 1) % count;
+            }
+            Key::Named(NamedKey::ArrowLeft) if manual => {
+                self.manual_cell = (self.manual_cell + count - 1) % count;
+            }
+            Key::Named(NamedKey::ArrowDown) if manual => {
+                self.manual_cell = (self.manual_cell + cols) % count;
+            }
+            Key::Named(NamedKey::ArrowUp) if manual => {
+                self.manual_cell = (self.manual_cell + count - cols) % count;
+            }
+            Key::Named(NamedKey::ArrowRight) => self.params.set(RATE, (rate * 2.0).min(60.0)),
+            Key::Named(NamedKey::ArrowLeft) => self.params.set(RATE, (rate / 2.0).max(0.125)),
+            Key::Named(NamedKey::ArrowUp) => self.params.set(NITS, (nits * 2.0).min(10000.0)),
+            Key::Named(NamedKey::ArrowDown) => self.params.set(NITS, (nits / 2.0).max(1.0)),
+            _ => {}
+        }
+        None
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Params> {
+        Some(&mut self.params)
+    }
+}
@@ -0,0 +1,129 @@
+//! Tone-mapping operators that fit content mastered for one peak luminance
+//! onto a display with a lower peak.
+//!
+//! All operators take and return absolute luminance in nits. Content at the
+//! source peak lands on the target peak, and nothing is changed when the
+//! display is at least as bright as the master.
+
+use crate::transfer::pq;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    /// ITU-R BT.2390 EETF, a hermite roll-off in the PQ domain
+    Bt2390,
+    /// Reinhard with a white point at the source peak
+    Reinhard,
+    /// John Hable's filmic curve from Uncharted 2
+    Hable,
+    /// Krzysztof Narkowicz's fit of the ACES RRT + ODT
+    AcesFitted,
+}
+
+impl Operator {
+    pub const ALL: [Operator; 4] = [Operator::Bt2390, Operator::Reinhard, Operator::Hable, Operator::AcesFitted];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Operator::Bt2390 => "BT.2390 EETF",
+            Operator::Reinhard => "Extended Reinhard",
+            Operator::Hable => "Hable (Uncharted 2)",
+            Operator::AcesFitted => "ACES Fitted",
+        }
+    }
+
+    /// Map `nits` from content mastered at `source_peak` onto a display
+    /// whose peak is `target_peak`
+    pub fn apply(self, nits: f32, source_peak: f32, target_peak: f32) -> f32 {
+        let nits = nits.clamp(0.0, source_peak);
+        if source_peak <= target_peak {
+            return nits;
+        }
+
+        // The curve-based operators work on luminance relative to the display peak
+        let x = nits / target_peak;
+        let white = source_peak / target_peak;
+        let relative = match self {
+            Operator::Bt2390 => return bt2390_eetf(nits, source_peak, target_peak),
+            Operator::Reinhard => x * (1.0 + x / (white * white)) / (1.0 + x),
+            Operator::Hable => hable(HABLE_EXPOSURE * x) / hable(HABLE_EXPOSURE * white),
+            Operator::AcesFitted => aces_fitted(ACES_EXPOSURE * x) / aces_fitted(ACES_EXPOSURE * white),
+        };
+        relative.min(1.0) * target_peak
+    }
+}
+
+/// BT.2390 EETF with a zero black level on both source and target
+fn bt2390_eetf(nits: f32, source_peak: f32, target_peak: f32) -> f32 {
+    let source_max = pq::inverse_eotf(source_peak);
+    let e1 = pq::inverse_eotf(nits) / source_max;
+    let max_lum = pq::inverse_eotf(target_peak) / source_max;
+    let knee = 1.5 * max_lum - 0.5;
+
+    let e2 = if e1 < knee {
+        e1
+    } else {
+        let t = (e1 - knee) / (1.0 - knee);
+        let (t2, t3) = (t * t, t * t * t);
+        (2.0 * t3 - 3.0 * t2 + 1.0) * knee
+            + (t3 - 2.0 * t2 + t) * (1.0 - knee)
+            + (-2.0 * t3 + 3.0 * t2) * max_lum
+    };
+    pq::eotf(e2 * source_max).min(target_peak)
+}
+
+/// Exposure bias from Hable's original shader
+const HABLE_EXPOSURE: f32 = 2.0;
+
+fn hable(x: f32) -> f32 {
+    const A: f32 = 0.15;
+    const B: f32 = 0.50;
+    const C: f32 = 0.10;
+    const D: f32 = 0.20;
+    const E: f32 = 0.02;
+    const F: f32 = 0.30;
+    (x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F) - E / F
+}
+
+/// Exposure Narkowicz applies before the fitted curve
+const ACES_EXPOSURE: f32 = 0.6;
+
+fn aces_fitted(x: f32) -> f32 {
+    (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_peak_reaches_target_peak() {
+        for op in Operator::ALL {
+            for (source, target) in [(4000.0, 1000.0), (10000.0, 600.0)] {
+                let peak = op.apply(source, source, target);
+                assert!((peak - target).abs() < target * 1e-3, "{} {source}->{target}: {peak}", op.name());
+            }
+        }
+    }
+
+    #[test]
+    fn monotonic_and_bounded() {
+        for op in Operator::ALL {
+            let mut last = 0.0;
+            for i in 0..=1000 {
+                let out = op.apply(i as f32 * 4.0, 4000.0, 800.0);
+                assert!(out + 1e-3 >= last, "{} not monotonic at {}", op.name(), i * 4);
+                assert!(out <= 800.0 + 1e-3);
+                last = out;
+            }
+        }
+    }
+
+    #[test]
+    fn no_change_on_brighter_display() {
+        for op in Operator::ALL {
+            assert_eq!(op.apply(500.0, 1000.0, 1000.0), 500.0);
+        }
+        // BT.2390 leaves everything below the knee untouched
+        assert!((Operator::Bt2390.apply(100.0, 4000.0, 1000.0) - 100.0).abs() < 0.1);
+    }
+}
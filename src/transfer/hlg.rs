@@ -0,0 +1,126 @@
+//! Hybrid Log-Gamma (ARIB STD-B67 / ITU-R BT.2100).
+//!
+//! HLG signals are relative to the display: the same signal is rendered
+//! through an OOTF whose system gamma depends on the display's peak luminance.
+
+const A: f32 = 0.178_832_77;
+const B: f32 = 1.0 - 4.0 * A;
+const C: f32 = 0.559_910_7;
+
+/// BT.2100 luma weights, used by the OOTF
+const LUMA: [f32; 3] = [0.2627, 0.6780, 0.0593];
+
+/// Normalized scene-linear light (0..1) to HLG signal (0..1)
+pub fn oetf(scene: f32) -> f32 {
+    let e = scene.max(0.0);
+    if e <= 1.0 / 12.0 {
+        (3.0 * e).sqrt()
+    } else {
+        A * (12.0 * e - B).ln() + C
+    }
+}
+
+/// HLG signal (0..1) to normalized scene-linear light (0..1)
+pub fn inverse_oetf(signal: f32) -> f32 {
+    let e = signal.max(0.0);
+    if e <= 0.5 {
+        e * e / 3.0
+    } else {
+        (((e - C) / A).exp() + B) / 12.0
+    }
+}
+
+/// System gamma for a display of the given peak luminance. Uses the extended
+/// formula of BT.2390 throughout: it is within 0.012 of BT.2100's formula
+/// between 400 and 2000 nits and has no step at either end of that range.
+pub fn system_gamma(peak_nits: f32) -> f32 {
+    1.2 * 1.111f32.powf((peak_nits / 1000.0).log2())
+}
+
+/// Scene-linear RGB (0..1) to display light in nits
+pub fn ootf(scene: [f32; 3], peak_nits: f32) -> [f32; 3] {
+    let gamma = system_gamma(peak_nits);
+    let ys = LUMA[0] * scene[0] + LUMA[1] * scene[1] + LUMA[2] * scene[2];
+    let gain = if ys > 0.0 { peak_nits * ys.powf(gamma - 1.0) } else { 0.0 };
+    [scene[0] * gain, scene[1] * gain, scene[2] * gain]
+}
+
+/// HLG signal RGB (0..1) to display light in nits, including the black
+/// level lift BT.2100 applies for displays with a non-zero black
+pub fn eotf(signal: [f32; 3], peak_nits: f32, black_nits: f32) -> [f32; 3] {
+    let gamma = system_gamma(peak_nits);
+    let beta = (3.0 * (black_nits / peak_nits).powf(1.0 / gamma)).sqrt();
+    let scene = signal.map(|e| inverse_oetf((1.0 - beta) * e + beta));
+    ootf(scene, peak_nits)
+}
+
+/// Display luminance in nits of an achromatic HLG signal on a zero-black display
+pub fn signal_to_nits(signal: f32, peak_nits: f32) -> f32 {
+    eotf([signal; 3], peak_nits, 0.0)[1]
+}
+
+/// Achromatic HLG signal that a zero-black display shows at `nits`
+pub fn nits_to_signal(nits: f32, peak_nits: f32) -> f32 {
+    let gamma = system_gamma(peak_nits);
+    oetf((nits / peak_nits).max(0.0).powf(1.0 / gamma))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oetf_reference_points() {
+        assert!((oetf(1.0 / 12.0) - 0.5).abs() < 1e-6);
+        assert!((oetf(1.0) - 1.0).abs() < 1e-6);
+        for e in [0.0, 0.01, 0.05, 0.2, 0.5, 0.9] {
+            assert!((inverse_oetf(oetf(e)) - e).abs() < 1e-5, "{e}");
+        }
+    }
+
+    #[test]
+    fn system_gamma_at_reference_peak() {
+        assert!((system_gamma(1000.0) - 1.2).abs() < 1e-6);
+        assert!((system_gamma(2000.0) - 1.333).abs() < 1e-3);
+        // Close to the BT.2100 formula inside its range
+        for peak in [400.0f32, 600.0, 1500.0, 2000.0] {
+            let bt2100 = 1.2 + 0.42 * (peak / 1000.0).log10();
+            assert!((system_gamma(peak) - bt2100).abs() < 0.012, "{peak}");
+        }
+    }
+
+    #[test]
+    fn system_gamma_continuous_at_range_edges() {
+        for edge in [400.0f32, 2000.0] {
+            let below = system_gamma(edge - 0.1);
+            let at = system_gamma(edge);
+            let above = system_gamma(edge + 0.1);
+            assert!(below < at && at < above, "{edge}");
+            assert!(above - below < 1e-3, "{edge}");
+        }
+    }
+
+    #[test]
+    fn system_gamma_outside_bt2100_range() {
+        // BT.2390 extended formula: 1.2 * 1.111^log2(peak / 1000)
+        assert!((system_gamma(100.0) - 0.846).abs() < 1e-3);
+        assert!((system_gamma(4000.0) - 1.481).abs() < 1e-3);
+        assert!(system_gamma(100.0) < system_gamma(400.0));
+        assert!(system_gamma(4000.0) > system_gamma(2000.0));
+    }
+
+    #[test]
+    fn reference_white_on_1000_nit_display() {
+        // BT.2408: HLG 75% is reference white, 203 nits on a 1000 nit display
+        assert!((signal_to_nits(0.75, 1000.0) - 203.0).abs() < 0.5);
+        assert!((signal_to_nits(1.0, 1000.0) - 1000.0).abs() < 0.01);
+        assert_eq!(signal_to_nits(0.0, 1000.0), 0.0);
+    }
+
+    #[test]
+    fn grey_card_signal() {
+        // BT.2408: 18% grey is 26 nits at HLG 38% on a 1000 nit display
+        assert!((nits_to_signal(26.0, 1000.0) - 0.38).abs() < 0.005);
+        assert!((signal_to_nits(nits_to_signal(26.0, 1000.0), 1000.0) - 26.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,83 @@
+//! HDR transfer functions and integer code-value encodings.
+
+pub mod hlg;
+pub mod pq;
+
+/// Bit depth of an integer video signal
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitDepth {
+    Eight,
+    Ten,
+    Twelve,
+}
+
+impl BitDepth {
+    pub fn bits(self) -> u32 {
+        match self {
+            BitDepth::Eight => 8,
+            BitDepth::Ten => 10,
+            BitDepth::Twelve => 12,
+        }
+    }
+
+    /// Largest code value representable at this depth
+    pub fn max_code(self) -> u16 {
+        ((1u32 << self.bits()) - 1) as u16
+    }
+}
+
+/// Quantization range of an integer video signal
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Range {
+    /// Signal 0..1 spans every code (0-1023 at 10 bits)
+    Full,
+    /// Signal 0..1 spans the legal range (64-940 at 10 bits)
+    Narrow,
+}
+
+/// Bit depth and range used to turn a normalized signal into code values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CodeFormat {
+    pub bit_depth: BitDepth,
+    pub range: Range,
+}
+
+impl CodeFormat {
+    pub const fn new(bit_depth: BitDepth, range: Range) -> Self {
+        Self { bit_depth, range }
+    }
+
+    /// Code value for signal 0.0 (black)
+    pub fn black_code(self) -> u16 {
+        match self.range {
+            Range::Full => 0,
+            Range::Narrow => 16 << (self.bit_depth.bits() - 8),
+        }
+    }
+
+    /// Code value for signal 1.0 (nominal peak)
+    pub fn white_code(self) -> u16 {
+        match self.range {
+            Range::Full => self.bit_depth.max_code(),
+            Range::Narrow => 235 << (self.bit_depth.bits() - 8),
+        }
+    }
+
+    /// Quantize a normalized signal to the nearest code value.
+    /// Narrow range keeps the footroom/headroom codes reachable, so signals
+    /// slightly outside 0..1 still map to distinct codes.
+    pub fn quantize(self, signal: f64) -> u16 {
+        let black = self.black_code() as f64;
+        let white = self.white_code() as f64;
+        let code = (black + signal * (white - black)).round();
+        code.clamp(0.0, self.bit_depth.max_code() as f64) as u16
+    }
+
+    /// Normalized signal for a code value; narrow-range footroom and
+    /// headroom codes return values below 0.0 or above 1.0
+    pub fn dequantize(self, code: u16) -> f64 {
+        let black = self.black_code() as f64;
+        let white = self.white_code() as f64;
+        (code as f64 - black) / (white - black)
+    }
+}
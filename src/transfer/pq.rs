@@ -0,0 +1,111 @@
+//! SMPTE ST 2084 perceptual quantizer (PQ).
+
+use super::CodeFormat;
+
+/// Luminance of PQ signal 1.0
+pub const PEAK_NITS: f64 = 10000.0;
+
+const M1: f64 = 2610.0 / 16384.0;
+const M2: f64 = 2523.0 / 4096.0 * 128.0;
+const C1: f64 = 3424.0 / 4096.0;
+const C2: f64 = 2413.0 / 4096.0 * 32.0;
+const C3: f64 = 2392.0 / 4096.0 * 32.0;
+
+/// PQ signal (0..1) to absolute luminance in nits
+pub fn eotf_f64(signal: f64) -> f64 {
+    let p = signal.clamp(0.0, 1.0).powf(1.0 / M2);
+    let y = ((p - C1).max(0.0) / (C2 - C3 * p)).powf(1.0 / M1);
+    y * PEAK_NITS
+}
+
+/// Absolute luminance in nits to PQ signal (0..1)
+pub fn inverse_eotf_f64(nits: f64) -> f64 {
+    let y = (nits / PEAK_NITS).clamp(0.0, 1.0).powf(M1);
+    ((C1 + C2 * y) / (1.0 + C3 * y)).powf(M2)
+}
+
+/// PQ signal (0..1) to absolute luminance in nits
+pub fn eotf(signal: f32) -> f32 {
+    let (m1, m2) = (M1 as f32, M2 as f32);
+    let (c1, c2, c3) = (C1 as f32, C2 as f32, C3 as f32);
+    let p = signal.clamp(0.0, 1.0).powf(1.0 / m2);
+    let y = ((p - c1).max(0.0) / (c2 - c3 * p)).powf(1.0 / m1);
+    y * PEAK_NITS as f32
+}
+
+/// Absolute luminance in nits to PQ signal (0..1)
+pub fn inverse_eotf(nits: f32) -> f32 {
+    let (m1, m2) = (M1 as f32, M2 as f32);
+    let (c1, c2, c3) = (C1 as f32, C2 as f32, C3 as f32);
+    let y = (nits / PEAK_NITS as f32).clamp(0.0, 1.0).powf(m1);
+    ((c1 + c2 * y) / (1.0 + c3 * y)).powf(m2)
+}
+
+/// Luminance in nits of an integer PQ code value
+pub fn code_to_nits(code: u16, format: CodeFormat) -> f32 {
+    eotf_f64(format.dequantize(code)) as f32
+}
+
+/// Nearest integer PQ code value for a luminance in nits
+pub fn nits_to_code(nits: f32, format: CodeFormat) -> u16 {
+    format.quantize(inverse_eotf_f64(nits as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transfer::{BitDepth, Range};
+
+    const FULL_10: CodeFormat = CodeFormat::new(BitDepth::Ten, Range::Full);
+    const NARROW_10: CodeFormat = CodeFormat::new(BitDepth::Ten, Range::Narrow);
+
+    // Reference signal values from SMPTE ST 2084 / ITU-R BT.2100
+    const REFERENCE: [(f64, f64); 6] = [
+        (0.0, 0.0),
+        (0.1, 0.062_337),
+        (100.0, 0.508_078),
+        (203.0, 0.580_689),
+        (1000.0, 0.751_827),
+        (10000.0, 1.0),
+    ];
+
+    #[test]
+    fn inverse_eotf_matches_reference() {
+        for (nits, signal) in REFERENCE {
+            assert!((inverse_eotf_f64(nits) - signal).abs() < 1e-6, "{nits} nits");
+            assert!((inverse_eotf(nits as f32) as f64 - signal).abs() < 1e-5, "{nits} nits");
+        }
+    }
+
+    #[test]
+    fn eotf_round_trips() {
+        for (nits, signal) in REFERENCE {
+            assert!((eotf_f64(signal) - nits).abs() <= nits * 1e-5 + 1e-4, "{nits} nits");
+            assert!((eotf(signal as f32) as f64 - nits).abs() <= nits * 1e-3 + 1e-3, "{nits} nits");
+        }
+    }
+
+    #[test]
+    fn code_values() {
+        assert_eq!(nits_to_code(100.0, FULL_10), 520);
+        assert_eq!(nits_to_code(100.0, NARROW_10), 509);
+        assert_eq!(nits_to_code(1000.0, FULL_10), 769);
+        assert_eq!(nits_to_code(1000.0, NARROW_10), 723);
+        assert_eq!(nits_to_code(10000.0, NARROW_10), 940);
+        assert_eq!(nits_to_code(0.0, NARROW_10), 64);
+
+        let narrow_12 = CodeFormat::new(BitDepth::Twelve, Range::Narrow);
+        assert_eq!(narrow_12.black_code(), 256);
+        assert_eq!(narrow_12.white_code(), 3760);
+        let full_8 = CodeFormat::new(BitDepth::Eight, Range::Full);
+        assert_eq!(nits_to_code(10000.0, full_8), 255);
+    }
+
+    #[test]
+    fn narrow_footroom_is_black() {
+        assert!(NARROW_10.dequantize(4) < 0.0);
+        assert_eq!(code_to_nits(4, NARROW_10), 0.0);
+        assert_eq!(code_to_nits(64, NARROW_10), 0.0);
+        assert!((code_to_nits(1023, FULL_10) - 10000.0).abs() < 0.01);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::app::AppState;
+use crate::clock::ClockMode;
 use crate::dx12::Vertex;
+use crate::pages::Category;
+use crate::pages::params::{Kind, Params, Value};
 use egui::{Context, Event, FontId, PointerButton, RawInput, Pos2, Rect, TextureId, Vec2, ViewportId, ViewportInfo};
 use std::time::Instant;
 pub use egui::TexturesDelta;
@@ -192,13 +195,98 @@ fn render_ui(ctx: &Context, app: &mut AppState) {
                 if ui.button("< Prev").clicked() {
                     app.prev_page();
                 }
-                ui.label(format!("Page {}/{}", app.current_page + 1, app.page_count()));
+                match app.page_number() {
+                    Some(number) => ui.label(format!("Page {}/{}", number, app.page_count())),
+                    None => ui.label("Page hidden"),
+                };
                 if ui.button("Next >").clicked() {
                     app.next_page();
                 }
             });
 
-            ui.label(format!("Current: {}", app.current_page_name()));
+            let mut selected = None;
+            egui::ComboBox::from_label("Page")
+                .selected_text(app.current_page_name())
+                .height(600.0)
+                .show_ui(ui, |ui| {
+                    for category in Category::ALL {
+                        ui.strong(category.name());
+                        for (index, entry) in app.pages().iter().enumerate() {
+                            if entry.enabled
+                                && entry.category == category
+                                && ui
+                                    .selectable_label(index == app.current_page, entry.page.name())
+                                    .on_hover_text(entry.description)
+                                    .clicked()
+                            {
+                                selected = Some(index);
+                            }
+                        }
+                    }
+                });
+            if let Some(index) = selected {
+                app.select_page(index);
+            }
+            ui.label(app.current_page_entry().description);
+            app.current_page_ui(ui);
+            if let Some(params) = app.current_page_params() {
+                params_ui(ui, params);
+            }
+
+            ui.separator();
+
+            ui.heading("Clock");
+            let clock = &mut app.clock;
+            ui.horizontal(|ui| {
+                for mode in ClockMode::ALL {
+                    if ui.radio(clock.mode == mode, mode.name()).clicked() {
+                        clock.set_mode(mode);
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button("|<").clicked() {
+                    clock.reset();
+                }
+                if ui.button("< Step").clicked() {
+                    clock.step(-1);
+                }
+                if ui.button("Step >").clicked() {
+                    clock.step(1);
+                }
+                ui.label("Step rate:");
+                ui.add(egui::DragValue::new(&mut clock.step_rate_hz).range(1.0..=1000.0).suffix(" Hz"));
+            });
+            let mut time = clock.time();
+            let scrub_range = time.max(60.0);
+            if ui.add(egui::Slider::new(&mut time, 0.0..=scrub_range).text("Time (s)")).changed() {
+                clock.scrub(time);
+            }
+            ui.label(format!("Frame {}  t = {:.4} s", clock.frame(), clock.time()));
+
+            ui.separator();
+            ui.heading("Frame Timing");
+            let timing = &mut app.frame_timing;
+            match timing.refresh_hz {
+                Some(hz) => ui.label(format!(
+                    "Refresh {:.2} Hz, last interval {:.2} ms ({:+.2} ms)",
+                    hz, timing.last_interval_ms, timing.last_deviation_ms
+                )),
+                None => ui.label("Refresh rate unknown"),
+            };
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "Dropped frames: {}, max deviation {:.2} ms",
+                    timing.dropped_frames, timing.max_deviation_ms
+                ));
+                if ui.button("Reset").clicked() {
+                    timing.reset();
+                }
+            });
+            let mut logging = timing.is_logging();
+            if ui.checkbox(&mut logging, format!("Log to {}", timing.log_path.display())).changed() {
+                timing.set_logging(logging);
+            }
 
             ui.separator();
 
@@ -214,7 +302,54 @@ fn render_ui(ctx: &Context, app: &mut AppState) {
             ui.label("Controls:");
             ui.label("  PageUp/PageDown: Change page");
             ui.label("  Ctrl+U: Toggle UI");
+            ui.label("  Ctrl+P: Pause clock, Ctrl+Left/Right: Step frame");
+            ui.label("  Ctrl+F: Fixed step clock, Ctrl+0: Reset clock");
+            ui.label("  Other keys: Page controls, shown on the page");
+        });
+}
+
+/// Controls for a page's typed parameters
+fn params_ui(ui: &mut egui::Ui, params: &mut Params) {
+    for param in params.iter_mut() {
+        ui.horizontal(|ui| {
+            ui.label(param.label);
+            match (&param.kind, &mut param.value) {
+                (Kind::Float { min, max, logarithmic, suffix }, Value::Float(value)) => {
+                    ui.add(egui::Slider::new(value, *min..=*max).logarithmic(*logarithmic).suffix(*suffix));
+                }
+                (Kind::Int { min, max }, Value::Int(value)) => {
+                    ui.add(egui::Slider::new(value, *min..=*max));
+                }
+                (Kind::Choice(options), Value::Choice(index)) => {
+                    for (i, option) in options.iter().enumerate() {
+                        ui.radio_value(index, i, option.as_str());
+                    }
+                }
+                (Kind::Bool, Value::Bool(value)) => {
+                    ui.checkbox(value, "");
+                }
+                (Kind::Color, Value::Color(rgb)) => {
+                    ui.color_edit_button_rgb(rgb);
+                }
+                (Kind::NitsList { min, max }, Value::NitsList(values)) => {
+                    ui.horizontal_wrapped(|ui| {
+                        for value in values.iter_mut() {
+                            ui.add(egui::DragValue::new(value).range(*min..=*max).speed(1.0));
+                        }
+                        if ui.small_button("+").clicked() {
+                            values.push(values.last().copied().unwrap_or(*min));
+                        }
+                        if ui.add_enabled(values.len() > 1, egui::Button::new("-").small()).clicked() {
+                            values.pop();
+                        }
+                    });
+                }
+                _ => {
+                    ui.label("(value does not match kind)");
+                }
+            }
         });
+    }
 }
 
 fn shapes_to_vertices(